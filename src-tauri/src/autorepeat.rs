@@ -0,0 +1,40 @@
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::shortcuts;
+use crate::Action;
+
+// Id of the held shortcut and the generation of its repeat timer, if any.
+// The OS doesn't repeat global shortcuts, so we do it ourselves.
+static REPEATING: Lazy<Mutex<Option<(u32, u64)>>> = Lazy::new(|| Mutex::new(None));
+
+// Bumped for every press so stale timers can tell they're no longer relevant
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Keeps triggering `action` while `shortcut` is held: first after `delay`,
+/// then every `interval`, until [`stop`] is called for the shortcut.
+pub fn start(shortcut: Shortcut, shortcut_id: String, action: Action, delay: Duration, interval: Duration) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    *REPEATING.lock().unwrap() = Some((shortcut.id(), generation));
+
+    let is_current = move || *REPEATING.lock().unwrap() == Some((shortcut.id(), generation));
+
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        while is_current() {
+            shortcuts::trigger_action(&shortcut_id, &action, &shortcut, Instant::now());
+            std::thread::sleep(interval);
+        }
+    });
+}
+
+/// Stops repeating once `shortcut_id` is released
+pub fn stop(shortcut_id: u32) {
+    let mut repeating = REPEATING.lock().unwrap();
+    if repeating.is_some_and(|(id, _)| id == shortcut_id) {
+        *repeating = None;
+    }
+}
@@ -0,0 +1,474 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+use crate::config;
+use crate::profiles::{self, SettingsFile, DEFAULT_PROFILE};
+use crate::shortcuts::{self, ShortcutManager, ShortcutsConfig};
+
+// Identifies Galaxy bundles among other JSON files
+const BUNDLE_FORMAT: &str = "galaxy-config";
+
+// Version of the bundle envelope; the settings inside carry their own version
+const BUNDLE_VERSION: u32 = 1;
+
+/// A portable copy of the whole settings file, every profile included, for
+/// sharing a setup between machines.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub format: String,
+    pub version: u32,
+    /// Galaxy version that wrote the bundle
+    #[serde(rename = "appVersion")]
+    pub app_version: String,
+    /// The settings file, migrated to the current format on import
+    pub settings: Value,
+}
+
+/// How an imported bundle is combined with the current settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    /// Imported settings win, everything else is kept
+    Merge,
+    /// The bundle becomes the whole settings file
+    Replace,
+}
+
+/// One setting that differs between the current and the imported settings
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingChange {
+    pub profile: String,
+    /// Dotted path of the setting, e.g. `shortcuts.leftHalf.bindings`
+    pub setting: String,
+    pub current: Option<Value>,
+    pub incoming: Option<Value>,
+}
+
+/// What importing a bundle would change
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreview {
+    pub active_profile: String,
+    pub incoming_active_profile: String,
+    pub added_profiles: Vec<String>,
+    pub removed_profiles: Vec<String>,
+    pub changes: Vec<SettingChange>,
+}
+
+/// Wraps the current settings in a bundle
+pub fn export(config: &ShortcutsConfig) -> Result<String, String> {
+    let settings = serde_json::to_value(profiles::settings_file(&config.overrides()))
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    let bundle = ConfigBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        settings,
+    };
+    serde_json::to_string_pretty(&bundle).map_err(|e| format!("Failed to serialize settings: {}", e))
+}
+
+/// Reads and validates a bundle, returning its settings as written, migrated
+/// to the current format
+pub fn read(path: &Path) -> Result<Value, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let bundle: ConfigBundle =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(format!("{:?} is not a Galaxy settings bundle", path));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Bundle version {} is newer than the supported version {}",
+            bundle.version, BUNDLE_VERSION
+        ));
+    }
+
+    let (settings, _) = config::migrate(bundle.settings)?;
+    validate(&parse(settings.clone())?)?;
+    Ok(settings)
+}
+
+fn parse(settings: Value) -> Result<SettingsFile, String> {
+    serde_json::from_value(settings).map_err(|e| format!("Invalid settings in bundle: {}", e))
+}
+
+// Checks every profile of `file` the same way a hot reload would
+fn validate(file: &SettingsFile) -> Result<(), String> {
+    if let Some(active) = &file.active_profile {
+        if active != DEFAULT_PROFILE && !file.profiles.contains_key(active) {
+            return Err(format!("Active profile {} is not in the bundle", active));
+        }
+    }
+
+    let profiles = std::iter::once((DEFAULT_PROFILE, &file.base))
+        .chain(file.profiles.iter().map(|(name, profile)| (name.as_str(), profile)));
+    for (name, profile) in profiles {
+        shortcuts::validate_config(&ShortcutsConfig::from_overrides(profile.clone()))
+            .map_err(|e| format!("Profile {}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+// Lists every profile of a settings file by name, the default one included
+fn all_profiles(file: &SettingsFile) -> BTreeMap<String, ShortcutsConfig> {
+    let mut profiles = file.profiles.clone();
+    profiles.insert(DEFAULT_PROFILE.to_string(), file.base.clone());
+    profiles
+}
+
+// Settings whose entries are merged one by one rather than replaced as a whole
+const MERGED_BY_ENTRY: &[&str] = &["shortcuts", "profileShortcuts", "layouts", "keys"];
+
+// Merges imported settings, as written in the settings file, into the current
+// ones. Only the settings the import contains are taken, so anything it
+// leaves out keeps its current value instead of the default. Profiles and
+// window mode are merged setting by setting.
+fn merge(current: &mut Map<String, Value>, incoming: Map<String, Value>) {
+    for (key, value) in incoming {
+        match (current.get_mut(&key), value) {
+            (Some(Value::Object(profiles)), Value::Object(entries)) if key == "profiles" => {
+                for (name, profile) in entries {
+                    match (profiles.get_mut(&name), profile) {
+                        (Some(Value::Object(existing)), Value::Object(profile)) => merge(existing, profile),
+                        (_, profile) => {
+                            profiles.insert(name, profile);
+                        }
+                    }
+                }
+            }
+            (Some(Value::Object(existing)), Value::Object(entries)) if MERGED_BY_ENTRY.contains(&key.as_str()) => {
+                existing.extend(entries);
+            }
+            (Some(Value::Object(existing)), Value::Object(settings)) if key == "windowMode" => {
+                merge(existing, settings);
+            }
+            (_, value) => {
+                current.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Combines the current settings with imported ones, as returned by [`read`]
+pub fn combine(current: &SettingsFile, incoming: Value, mode: ImportMode) -> Result<SettingsFile, String> {
+    if mode == ImportMode::Replace {
+        return parse(incoming);
+    }
+
+    let mut settings =
+        serde_json::to_value(current).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let (Value::Object(settings), Value::Object(incoming)) = (&mut settings, incoming) {
+        merge(settings, incoming);
+    }
+    parse(settings)
+}
+
+// Flattens a settings value into dotted paths; arrays are compared as a whole
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, value, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+// Returns the effective settings of a profile as dotted paths
+fn flat_settings(profile: &ShortcutsConfig) -> BTreeMap<String, Value> {
+    let mut out = BTreeMap::new();
+    if let Ok(value) = serde_json::to_value(ShortcutsConfig::from_overrides(profile.clone())) {
+        flatten("", &value, &mut out);
+    }
+    out
+}
+
+/// Lists what turning `current` into `result` changes
+pub fn diff(current: &SettingsFile, result: &SettingsFile) -> ImportPreview {
+    let current_profiles = all_profiles(current);
+    let result_profiles = all_profiles(result);
+    let active = |file: &SettingsFile| file.active_profile.clone().unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+    let mut changes = Vec::new();
+    for (name, profile) in &result_profiles {
+        let Some(existing) = current_profiles.get(name) else {
+            continue;
+        };
+        let before = flat_settings(existing);
+        let after = flat_settings(profile);
+
+        let settings: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+        for setting in settings {
+            let (current, incoming) = (before.get(setting), after.get(setting));
+            if current != incoming {
+                changes.push(SettingChange {
+                    profile: name.clone(),
+                    setting: setting.clone(),
+                    current: current.cloned(),
+                    incoming: incoming.cloned(),
+                });
+            }
+        }
+    }
+
+    ImportPreview {
+        active_profile: active(current),
+        incoming_active_profile: active(result),
+        added_profiles: result_profiles
+            .keys()
+            .filter(|name| !current_profiles.contains_key(*name))
+            .cloned()
+            .collect(),
+        removed_profiles: current_profiles
+            .keys()
+            .filter(|name| !result_profiles.contains_key(*name))
+            .cloned()
+            .collect(),
+        changes,
+    }
+}
+
+/// Writes the current settings, every profile included, to a bundle file
+#[tauri::command]
+pub async fn export_config(path: String, state: State<'_, ShortcutManager>) -> Result<(), String> {
+    let config = state.shortcuts.lock().map_err(|e| e.to_string())?;
+    config::write_atomic(Path::new(&path), &export(&config)?)?;
+    println!("Exported settings to {}", path);
+    Ok(())
+}
+
+/// Shows what importing a bundle would change, without changing anything
+#[tauri::command]
+pub async fn preview_import(
+    path: String,
+    mode: ImportMode,
+    state: State<'_, ShortcutManager>,
+) -> Result<ImportPreview, String> {
+    let incoming = read(Path::new(&path))?;
+    let config = state.shortcuts.lock().map_err(|e| e.to_string())?;
+    let current = profiles::settings_file(&config.overrides());
+    let result = combine(&current, incoming, mode)?;
+    Ok(diff(&current, &result))
+}
+
+/// Imports a bundle by merging it into or replacing the current settings.
+///
+/// The shortcuts of the resulting active profile are swapped in as a single
+/// transaction; if that fails, the current settings stay untouched.
+#[tauri::command]
+pub async fn import_config(
+    app_handle: AppHandle,
+    path: String,
+    mode: ImportMode,
+    state: State<'_, ShortcutManager>,
+) -> Result<ImportPreview, String> {
+    let incoming = read(Path::new(&path))?;
+    let current = profiles::settings_file(&state.shortcuts.lock().map_err(|e| e.to_string())?.overrides());
+    let result = combine(&current, incoming, mode)?;
+    let preview = diff(&current, &result);
+
+    let new_config = ShortcutsConfig::from_overrides(profiles::store(result));
+    if let Err(e) = shortcuts::replace_config(&app_handle, &state.shortcuts, new_config, ShortcutsConfig::save) {
+        profiles::store(current);
+        return Err(e);
+    }
+
+    println!("Imported settings from {} ({} changes)", path, preview.changes.len());
+    crate::refresh_tray_menu(&app_handle);
+    crate::refresh_tray_tooltip(&app_handle);
+    Ok(preview)
+}
+
+/// Imports a bundle into the settings file without starting the app, for the
+/// command line. Only previews the changes when `dry_run` is set.
+pub fn import_to_file(path: &Path, mode: ImportMode, dry_run: bool) -> Result<ImportPreview, String> {
+    let incoming = read(path)?;
+    let config = ShortcutsConfig::load().map_err(|e| e.to_string())?;
+    let current = profiles::settings_file(&config.overrides());
+    let result = combine(&current, incoming, mode)?;
+    let preview = diff(&current, &result);
+
+    if !dry_run {
+        ShortcutsConfig::from_overrides(profiles::store(result)).save()?;
+    }
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn settings(value: Value) -> SettingsFile {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn bundle(settings: Value) -> Value {
+        json!({ "format": BUNDLE_FORMAT, "version": BUNDLE_VERSION, "appVersion": "0.1.0", "settings": settings })
+    }
+
+    // Writes `bundle` to a file of its own and reads it back
+    fn read_bundle(name: &str, bundle: Value) -> Result<Value, String> {
+        let path = std::env::temp_dir().join(format!("galaxy-bundle-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, bundle.to_string()).unwrap();
+        let result = read(&path);
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    fn current() -> SettingsFile {
+        settings(json!({
+            "version": config::CURRENT_VERSION,
+            "chordTimeoutMs": 900,
+            "pauseShortcut": "",
+            "windowMode": { "shortcut": "Control+Alt+W", "timeoutMs": 3000, "keys": { "H": "leftHalf" } },
+            "shortcuts": { "leftHalf": { "name": "Left Half", "bindings": ["Control+Alt+J"] } },
+            "profiles": {
+                "laptop": { "repeatDelayMs": 250, "shortcuts": {} }
+            }
+        }))
+    }
+
+    #[test]
+    fn merging_keeps_the_settings_the_bundle_leaves_out() {
+        let incoming = json!({
+            "version": config::CURRENT_VERSION,
+            "windowMode": { "keys": { "L": "rightHalf" } },
+            "shortcuts": { "rightHalf": { "name": "Right Half", "bindings": ["Control+Alt+K"] } },
+            "profiles": { "laptop": { "chordTimeoutMs": 700 } }
+        });
+        let merged = combine(&current(), incoming, ImportMode::Merge).unwrap();
+
+        assert_eq!(merged.base.chord_timeout_ms, 900);
+        assert_eq!(merged.base.pause_shortcut, "");
+        assert_eq!(merged.base.window_mode.shortcut, "Control+Alt+W");
+        assert_eq!(merged.base.window_mode.timeout_ms, 3000);
+        assert_eq!(
+            merged.base.window_mode.keys,
+            BTreeMap::from([("H".to_string(), "leftHalf".to_string()), ("L".to_string(), "rightHalf".to_string())])
+        );
+        assert_eq!(merged.base.shortcuts["leftHalf"].bindings, ["Control+Alt+J"]);
+        assert_eq!(merged.base.shortcuts["rightHalf"].bindings, ["Control+Alt+K"]);
+
+        let laptop = &merged.profiles["laptop"];
+        assert_eq!(laptop.repeat_delay_ms, 250);
+        assert_eq!(laptop.chord_timeout_ms, 700);
+    }
+
+    #[test]
+    fn merging_takes_the_settings_the_bundle_sets() {
+        let incoming = json!({
+            "version": config::CURRENT_VERSION,
+            "activeProfile": "work",
+            "chordTimeoutMs": 500,
+            "windowMode": { "shortcut": "" },
+            "shortcuts": { "leftHalf": { "name": "Left Half", "bindings": [] } },
+            "profiles": { "work": { "pauseShortcut": "Control+Alt+Shift+F12" } }
+        });
+        let merged = combine(&current(), incoming, ImportMode::Merge).unwrap();
+
+        assert_eq!(merged.active_profile.as_deref(), Some("work"));
+        assert_eq!(merged.base.chord_timeout_ms, 500);
+        assert_eq!(merged.base.window_mode.shortcut, "");
+        assert!(merged.base.shortcuts["leftHalf"].bindings.is_empty());
+        assert_eq!(merged.profiles["work"].pause_shortcut, "Control+Alt+Shift+F12");
+        assert!(merged.profiles.contains_key("laptop"));
+    }
+
+    #[test]
+    fn replacing_takes_the_bundle_as_it_is() {
+        let incoming = json!({ "version": config::CURRENT_VERSION, "shortcuts": {} });
+        let replaced = combine(&current(), incoming, ImportMode::Replace).unwrap();
+
+        assert_eq!(replaced.base.chord_timeout_ms, ShortcutsConfig::default().chord_timeout_ms);
+        assert!(replaced.base.shortcuts.is_empty());
+        assert!(replaced.profiles.is_empty());
+    }
+
+    #[test]
+    fn diff_lists_changed_settings_and_profiles() {
+        let current = current();
+        let result = settings(json!({
+            "version": config::CURRENT_VERSION,
+            "activeProfile": "work",
+            "chordTimeoutMs": 900,
+            "pauseShortcut": "Control+Alt+Shift+P",
+            "windowMode": { "shortcut": "Control+Alt+W", "timeoutMs": 3000, "keys": { "H": "leftHalf" } },
+            "shortcuts": { "leftHalf": { "name": "Left Half", "bindings": ["Control+Alt+J"] } },
+            "profiles": { "work": {} }
+        }));
+        let preview = diff(&current, &result);
+
+        assert_eq!(preview.active_profile, DEFAULT_PROFILE);
+        assert_eq!(preview.incoming_active_profile, "work");
+        assert_eq!(preview.added_profiles, ["work"]);
+        assert_eq!(preview.removed_profiles, ["laptop"]);
+        assert_eq!(preview.changes.len(), 1);
+        let change = &preview.changes[0];
+        assert_eq!((change.profile.as_str(), change.setting.as_str()), (DEFAULT_PROFILE, "pauseShortcut"));
+        assert_eq!(change.current, Some(json!("")));
+        assert_eq!(change.incoming, Some(json!("Control+Alt+Shift+P")));
+    }
+
+    #[test]
+    fn reading_migrates_old_settings() {
+        let settings = read_bundle(
+            "old",
+            bundle(json!({
+                "version": 1,
+                "shortcuts": { "leftHalf": { "name": "Left Half", "defaultShortcut": "Ctrl+Alt+-" } }
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(settings["version"], json!(config::CURRENT_VERSION));
+        assert_eq!(settings["shortcuts"]["leftHalf"]["bindings"], json!(["Control+Alt+Minus"]));
+    }
+
+    #[test]
+    fn reading_rejects_other_files() {
+        let mut other = bundle(json!({ "version": config::CURRENT_VERSION, "shortcuts": {} }));
+        other["format"] = json!("something-else");
+        let error = read_bundle("format", other).unwrap_err();
+        assert!(error.contains("is not a Galaxy settings bundle"), "{error}");
+    }
+
+    #[test]
+    fn reading_rejects_newer_bundles_and_settings() {
+        let mut newer = bundle(json!({ "version": config::CURRENT_VERSION, "shortcuts": {} }));
+        newer["version"] = json!(BUNDLE_VERSION + 1);
+        let error = read_bundle("bundle-version", newer).unwrap_err();
+        assert!(error.contains("Bundle version"), "{error}");
+
+        let newer = bundle(json!({ "version": config::CURRENT_VERSION + 1, "shortcuts": {} }));
+        let error = read_bundle("settings-version", newer).unwrap_err();
+        assert!(error.contains("newer than the supported version"), "{error}");
+    }
+
+    #[test]
+    fn reading_rejects_invalid_settings() {
+        let invalid = bundle(json!({
+            "version": config::CURRENT_VERSION,
+            "shortcuts": { "leftHalf": { "name": "Left Half", "bindings": ["Control+Nope"] } }
+        }));
+        assert!(read_bundle("shortcut", invalid).is_err());
+
+        let missing = bundle(json!({ "version": config::CURRENT_VERSION, "activeProfile": "work", "shortcuts": {} }));
+        let error = read_bundle("profile", missing).unwrap_err();
+        assert!(error.contains("is not in the bundle"), "{error}");
+    }
+}
@@ -0,0 +1,127 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::shortcuts;
+use crate::Action;
+
+// Pressing this while a chord is pending cancels it
+const CANCEL_KEY: &str = "Escape";
+
+struct PendingChord {
+    generation: u64,
+    keys: HashMap<u32, (String, Action)>,
+    // Second keys we registered for this chord and must release afterwards
+    grabbed: Vec<Shortcut>,
+}
+
+// The chord waiting for its second key, if any
+static PENDING_CHORD: Lazy<Mutex<Option<PendingChord>>> = Lazy::new(|| Mutex::new(None));
+
+// Bumped for every chord so stale timeouts can tell they're no longer relevant
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Starts a chord after its leader was pressed.
+///
+/// The possible second keys (and `Escape`) are only grabbed from the OS while
+/// the chord is pending. The chord ends when any shortcut is pressed (see
+/// [`finish`]) or after `timeout`.
+///
+/// Must not register shortcuts synchronously: this runs inside the global
+/// shortcut handler, which holds the plugin's lock.
+pub fn begin(
+    app_handle: &AppHandle,
+    leader: &Shortcut,
+    keys: &HashMap<u32, (Shortcut, String, Action)>,
+    timeout: Duration,
+) {
+    // A chord started while another is pending replaces it
+    let _ = finish(app_handle, leader.id());
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let mut to_grab: Vec<Shortcut> = keys.values().map(|(shortcut, _, _)| *shortcut).collect();
+    if let Ok(cancel) = Shortcut::from_str(CANCEL_KEY) {
+        to_grab.push(cancel);
+    }
+
+    *PENDING_CHORD.lock().unwrap() = Some(PendingChord {
+        generation,
+        keys: keys
+            .iter()
+            .map(|(id, (_, action_id, action))| (*id, (action_id.clone(), action.clone())))
+            .collect(),
+        grabbed: Vec::new(),
+    });
+    println!("Chord started with {}, waiting for the next key...", leader);
+
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        let grabbed = shortcuts::grab_keys(&app_handle, to_grab);
+
+        // Never call into the plugin while holding the lock, the handler needs it too
+        let stale = {
+            let mut pending = PENDING_CHORD.lock().unwrap();
+            match pending.as_mut() {
+                Some(chord) if chord.generation == generation => {
+                    chord.grabbed = grabbed;
+                    None
+                }
+                // The chord already ended while we were grabbing keys
+                _ => Some(grabbed),
+            }
+        };
+        if let Some(grabbed) = stale {
+            shortcuts::release_keys(&app_handle, grabbed);
+        }
+        crate::refresh_tray_tooltip(&app_handle);
+
+        std::thread::sleep(timeout);
+
+        let expired = {
+            let mut pending = PENDING_CHORD.lock().unwrap();
+            match pending.as_ref() {
+                Some(chord) if chord.generation == generation => pending.take(),
+                _ => None,
+            }
+        };
+        if let Some(chord) = expired {
+            println!("Chord timed out");
+            shortcuts::release_keys(&app_handle, chord.grabbed);
+            crate::refresh_tray_tooltip(&app_handle);
+        }
+    });
+}
+
+/// Ends the pending chord, if any, and returns the action bound to
+/// `shortcut_id` as its second key.
+pub fn finish(app_handle: &AppHandle, shortcut_id: u32) -> Option<(String, Action)> {
+    let chord = PENDING_CHORD.lock().unwrap().take()?;
+    release(app_handle, chord.grabbed);
+    chord.keys.get(&shortcut_id).cloned()
+}
+
+/// Ends the pending chord, if any, without triggering anything
+pub fn cancel(app_handle: &AppHandle) {
+    if let Some(chord) = PENDING_CHORD.lock().unwrap().take() {
+        release(app_handle, chord.grabbed);
+    }
+}
+
+// Hands the keys grabbed for a chord back on another thread
+fn release(app_handle: &AppHandle, grabbed: Vec<Shortcut>) {
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        shortcuts::release_keys(&app_handle, grabbed);
+        crate::refresh_tray_tooltip(&app_handle);
+    });
+}
+
+/// Returns true while a chord is waiting for its second key
+pub fn is_pending() -> bool {
+    PENDING_CHORD.lock().unwrap().is_some()
+}
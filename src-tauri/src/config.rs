@@ -0,0 +1,372 @@
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::profiles::SettingsFile;
+use crate::shortcuts;
+
+// Settings file path given on the command line, if any
+static CONFIG_PATH_OVERRIDE: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+// What this process last wrote to each file, so the settings watcher can tell
+// Galaxy's own saves from outside edits
+static LAST_WRITTEN: Lazy<Mutex<HashMap<PathBuf, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+const CONFIG_DIR_NAME: &str = "Galaxy";
+const CONFIG_FILE_NAME: &str = "settings.json";
+
+/// Overrides the settings file location (used by the `--config` CLI flag)
+pub fn set_config_path(path: Option<PathBuf>) {
+    *CONFIG_PATH_OVERRIDE.lock().unwrap() = path;
+}
+
+/// Returns the path of the user settings file.
+///
+/// Defaults to `<platform config dir>/Galaxy/settings.json`, e.g.
+/// `%APPDATA%\Galaxy\settings.json` on Windows.
+pub fn config_path() -> PathBuf {
+    if let Some(path) = CONFIG_PATH_OVERRIDE.lock().unwrap().clone() {
+        return path;
+    }
+
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(CONFIG_DIR_NAME)
+        .join(CONFIG_FILE_NAME)
+}
+
+/// Returns the path the previous version of `path` is kept at
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".bak");
+    path.with_file_name(file_name)
+}
+
+/// Writes `contents` to `path` atomically.
+///
+/// The data is first written and flushed to a temporary file next to the
+/// target, the current file (if any) is copied to a `.bak` backup, and the
+/// temporary file is then renamed over the target. A crash at any point leaves
+/// either the old or the new file in place, never a truncated one.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    {
+        let mut file = fs::File::create(&tmp_path)
+            .map_err(|e| format!("Failed to create {:?}: {}", tmp_path, e))?;
+        file.write_all(contents.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| format!("Failed to write {:?}: {}", tmp_path, e))?;
+    }
+
+    if path.exists() {
+        let backup = backup_path(path);
+        fs::copy(path, &backup)
+            .map_err(|e| format!("Failed to back up {:?} to {:?}: {}", path, backup, e))?;
+    }
+
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to replace {:?}: {}", path, e)
+    })?;
+
+    LAST_WRITTEN.lock().unwrap().insert(path.to_path_buf(), contents.to_string());
+    Ok(())
+}
+
+/// Returns true if `path` holds exactly what this process last wrote to it
+pub fn is_own_write(path: &Path) -> bool {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return false;
+    };
+    LAST_WRITTEN.lock().unwrap().get(path) == Some(&contents)
+}
+
+/// Reads the settings file at `path`, upgrading it to [`CURRENT_VERSION`].
+///
+/// An older file is written back in the current format, with the original
+/// kept as a `.bak` backup, so the migration only runs once.
+pub fn load_from(path: &Path) -> Result<SettingsFile, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read settings: {}\nPath: {:?}", e, path))?;
+    let raw: Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+    let (raw, migrated) = migrate(raw)?;
+    let file: SettingsFile = serde_json::from_value(raw.clone())
+        .map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+
+    if migrated {
+        let json = serde_json::to_string_pretty(&raw)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        match write_atomic(path, &json) {
+            Ok(()) => println!("Upgraded {:?}, the previous version is kept as a backup", path),
+            Err(e) => eprintln!("Failed to save the upgraded settings file: {}", e),
+        }
+    }
+
+    Ok(file)
+}
+
+/// Version of the settings file format written by this build
+pub const CURRENT_VERSION: u32 = 3;
+
+// Each migration upgrades a settings document from version `index` to `index + 1`
+type Migration = fn(Value) -> Result<Value, String>;
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1, migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// Upgrades a raw settings document to [`CURRENT_VERSION`].
+///
+/// Files without a `version` field are treated as version 0. Returns the
+/// upgraded document and whether any migration was applied.
+pub fn migrate(mut value: Value) -> Result<(Value, bool), String> {
+    let version = match value.get("version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .ok_or_else(|| format!("Invalid settings version: {}", v))? as u32,
+    };
+
+    if version > CURRENT_VERSION {
+        return Err(format!(
+            "Settings file version {} is newer than the supported version {}",
+            version, CURRENT_VERSION
+        ));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        println!("Migrating settings from version {} to {}", from, from + 1);
+        value = migration(value)?;
+        value["version"] = Value::from(from as u32 + 1);
+    }
+
+    Ok((value, version < CURRENT_VERSION))
+}
+
+/// Version 0 was the unversioned `shortcuts.json` that held every shortcut.
+/// Version 1 only differs by the `version` field; entries matching the
+/// defaults are dropped the next time the file is saved.
+fn migrate_v0_to_v1(value: Value) -> Result<Value, String> {
+    if !value.get("shortcuts").is_some_and(Value::is_object) {
+        return Err("Settings file has no \"shortcuts\" object".to_string());
+    }
+    Ok(value)
+}
+
+/// Version 2 allows several shortcuts per action: each entry's
+/// `defaultShortcut` string became a `bindings` list.
+fn migrate_v1_to_v2(mut value: Value) -> Result<Value, String> {
+    let Some(shortcuts) = value.get_mut("shortcuts").and_then(Value::as_object_mut) else {
+        return Err("Settings file has no \"shortcuts\" object".to_string());
+    };
+
+    for entry in shortcuts.values_mut().filter_map(Value::as_object_mut) {
+        if let Some(shortcut) = entry.remove("defaultShortcut") {
+            entry.insert("bindings".to_string(), Value::Array(vec![shortcut]));
+        }
+    }
+    Ok(value)
+}
+
+/// Version 3 stores keys by their physical position, so shortcuts written
+/// with characters such as `Control+Alt+[` are rewritten to key names like
+/// `Control+Alt+BracketLeft`, in the top-level settings and every profile.
+fn migrate_v2_to_v3(mut value: Value) -> Result<Value, String> {
+    if let Some(profiles) = value.get_mut("profiles").and_then(Value::as_object_mut) {
+        profiles.values_mut().for_each(canonicalize_keys);
+    }
+    canonicalize_keys(&mut value);
+    Ok(value)
+}
+
+// Rewrites every shortcut of one profile in canonical form. Invalid shortcuts
+// are left as they are so loading reports them.
+fn canonicalize_keys(profile: &mut Value) {
+    let canonical = |shortcut: &mut Value| {
+        if let Some(canonical) = shortcut.as_str().and_then(|s| shortcuts::canonical_binding(s).ok()) {
+            *shortcut = Value::from(canonical);
+        }
+    };
+
+    if let Some(entries) = profile.get_mut("shortcuts").and_then(Value::as_object_mut) {
+        for entry in entries.values_mut() {
+            if let Some(bindings) = entry.get_mut("bindings").and_then(Value::as_array_mut) {
+                bindings.iter_mut().for_each(canonical);
+            }
+        }
+    }
+    if let Some(window_mode) = profile.get_mut("windowMode").and_then(Value::as_object_mut) {
+        if let Some(shortcut) = window_mode.get_mut("shortcut") {
+            canonical(shortcut);
+        }
+        if let Some(keys) = window_mode.get_mut("keys").and_then(Value::as_object_mut) {
+            *keys = std::mem::take(keys)
+                .into_iter()
+                .map(|(key, id)| (shortcuts::canonical_binding(&key).unwrap_or(key), id))
+                .collect();
+        }
+    }
+    if let Some(profile_shortcuts) = profile.get_mut("profileShortcuts").and_then(Value::as_object_mut) {
+        profile_shortcuts.values_mut().for_each(canonical);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const V0: &str = include_str!("../fixtures/settings-v0.json");
+    const V1: &str = include_str!("../fixtures/settings-v1.json");
+    const V2: &str = include_str!("../fixtures/settings-v2.json");
+
+    fn migrated(fixture: &str) -> Value {
+        let (value, migrated) = migrate(serde_json::from_str(fixture).unwrap()).unwrap();
+        assert!(migrated);
+        value
+    }
+
+    #[test]
+    fn unversioned_file_is_migrated_to_the_current_version() {
+        assert_eq!(
+            migrated(V0),
+            json!({
+                "version": CURRENT_VERSION,
+                "shortcuts": {
+                    "leftHalf": { "name": "Snap to Left Half", "bindings": ["Control+Alt+BracketLeft"] },
+                    "rightHalf": { "name": "Snap to Right Half", "bindings": ["Control+Alt+ArrowRight"] }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn version_1_gets_a_bindings_list() {
+        assert_eq!(
+            migrated(V1),
+            json!({
+                "version": CURRENT_VERSION,
+                "shortcuts": {
+                    "leftHalf": { "name": "Left Half", "bindings": ["Control+Alt+Minus"] }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn version_2_keys_are_canonicalized_in_every_profile() {
+        assert_eq!(
+            migrated(V2),
+            json!({
+                "version": CURRENT_VERSION,
+                "activeProfile": "laptop",
+                "shortcuts": {
+                    "leftHalf": {
+                        "name": "Left Half",
+                        "bindings": ["Control+Alt+BracketLeft", "Shift+Super+ArrowLeft"]
+                    }
+                },
+                "windowMode": {
+                    "shortcut": "Control+Alt+Space",
+                    "keys": { "Equal": "maximize", "H": "leftHalf" }
+                },
+                "profiles": {
+                    "laptop": {
+                        "shortcuts": {
+                            "rightHalf": { "name": "Right Half", "bindings": ["Control+Alt+BracketRight"] }
+                        },
+                        "profileShortcuts": { "default": "Control+Alt+Semicolon" }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn current_version_is_left_alone() {
+        let current = migrated(V2);
+        let (value, migrated) = migrate(current.clone()).unwrap();
+        assert!(!migrated);
+        assert_eq!(value, current);
+    }
+
+    #[test]
+    fn future_version_is_rejected() {
+        let error = migrate(json!({ "version": CURRENT_VERSION + 1, "shortcuts": {} })).unwrap_err();
+        assert!(error.contains("newer than the supported version"), "{error}");
+    }
+
+    #[test]
+    fn invalid_version_is_rejected() {
+        assert!(migrate(json!({ "version": "3", "shortcuts": {} })).is_err());
+        assert!(migrate(json!({ "shortcutz": {} })).is_err());
+    }
+
+    #[test]
+    fn own_writes_are_told_apart_from_edits() {
+        let path = std::env::temp_dir().join(format!("galaxy-own-write-{}.json", std::process::id()));
+        write_atomic(&path, "{}").unwrap();
+        assert!(is_own_write(&path));
+
+        fs::write(&path, "{ \"version\": 3 }").unwrap();
+        assert!(!is_own_write(&path));
+
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(backup_path(&path));
+        assert!(!is_own_write(&path));
+    }
+
+    #[test]
+    fn loading_an_old_file_rewrites_it_and_keeps_a_backup() {
+        let dir = std::env::temp_dir().join(format!("galaxy-migration-{}", std::process::id()));
+        let path = dir.join(CONFIG_FILE_NAME);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, V0).unwrap();
+
+        let file = load_from(&path).unwrap();
+        assert_eq!(file.version, CURRENT_VERSION);
+        assert_eq!(file.base.shortcuts["leftHalf"].bindings, ["Control+Alt+BracketLeft"]);
+
+        // The previous file is kept byte for byte, and the new one is in the
+        // current format
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), V0);
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, migrated(V0));
+        assert!(!path.with_file_name("settings.json.tmp").exists());
+
+        // Loading the rewritten file migrates nothing, so it isn't written again
+        let rewritten = fs::read_to_string(&path).unwrap();
+        load_from(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), rewritten);
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), V0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_files_are_left_alone() {
+        let dir = std::env::temp_dir().join(format!("galaxy-unreadable-{}", std::process::id()));
+        let path = dir.join(CONFIG_FILE_NAME);
+        fs::create_dir_all(&dir).unwrap();
+
+        for contents in ["{ \"version\": 1, ", "{ \"shortcutz\": {} }"] {
+            fs::write(&path, contents).unwrap();
+            assert!(load_from(&path).is_err());
+            assert_eq!(fs::read_to_string(&path).unwrap(), contents);
+            assert!(!backup_path(&path).exists());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(load_from(&path).is_err());
+    }
+}
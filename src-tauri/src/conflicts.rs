@@ -0,0 +1,186 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+use crate::registry;
+use crate::shortcuts::{
+    parse_binding, parse_shortcut, snapshot, ShortcutManager, ShortcutRegistrar, ShortcutsConfig,
+};
+
+// Combos Windows keeps for itself; registering them either fails or breaks the OS behaviour
+const RESERVED_SHORTCUTS: &[&str] = &[
+    "Control+Alt+Delete",
+    "Control+Shift+Escape",
+    "Control+Escape",
+    "Alt+Tab",
+    "Alt+Shift+Tab",
+    "Alt+Escape",
+    "Alt+F4",
+    "Super+D",
+    "Super+E",
+    "Super+L",
+    "Super+R",
+    "Super+Tab",
+    // Snapping and moving windows between monitors
+    "Super+ArrowLeft",
+    "Super+ArrowRight",
+    "Super+ArrowUp",
+    "Super+ArrowDown",
+    "Shift+Super+ArrowLeft",
+    "Shift+Super+ArrowRight",
+];
+
+/// Why a binding isn't working
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Conflict {
+    /// The shortcut string couldn't be parsed
+    Invalid { error: String },
+    /// Another Galaxy action uses the same combo
+    Duplicate { with: Vec<String> },
+    /// Windows reserves this combo
+    Reserved,
+    /// The OS refused the registration, usually because another application owns the combo
+    RegistrationFailed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutStatus {
+    pub id: String,
+    pub shortcut: String,
+    pub registered: bool,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Returns true if the combo (or a chord's leader) is reserved by Windows
+pub fn is_reserved(shortcut: &str) -> bool {
+    let Ok(binding) = parse_binding(shortcut) else {
+        return false;
+    };
+    RESERVED_SHORTCUTS
+        .iter()
+        .filter_map(|reserved| parse_shortcut(reserved).ok())
+        .any(|reserved| reserved.id() == binding.leader.id())
+}
+
+/// Returns the profile that `shortcut` (or a chord's leader) switches to, if any
+pub fn profile_shortcut(config: &ShortcutsConfig, shortcut: &str) -> Option<String> {
+    let binding = parse_binding(shortcut).ok()?;
+    config
+        .profile_shortcuts
+        .iter()
+        .find(|(_, other)| parse_shortcut(other).is_ok_and(|other| other.id() == binding.leader.id()))
+        .map(|(name, _)| name.clone())
+}
+
+/// Returns the ids of the other actions whose binding collides with `shortcut`
+pub fn find_duplicates(config: &ShortcutsConfig, shortcut_id: &str, shortcut: &str) -> Vec<String> {
+    let Ok(binding) = parse_binding(shortcut) else {
+        return Vec::new();
+    };
+    let mut duplicates: Vec<String> = config
+        .bindings()
+        .filter(|(id, other)| {
+            id.as_str() != shortcut_id
+                && parse_binding(other).is_ok_and(|other| other.collides_with(&binding))
+        })
+        .map(|(id, _)| id.clone())
+        .collect();
+    duplicates.sort();
+    duplicates.dedup();
+    duplicates
+}
+
+/// Returns true if `shortcut` (or a chord's leader) is the shortcut that enters window mode
+pub fn is_window_mode_shortcut(config: &ShortcutsConfig, shortcut: &str) -> bool {
+    let (Ok(binding), Some(activation)) = (parse_binding(shortcut), config.window_mode.activation_shortcut()) else {
+        return false;
+    };
+    binding.leader.id() == activation.id()
+}
+
+/// Returns true if `shortcut` (or a chord's leader) is the shortcut that pauses the others
+pub fn is_pause_shortcut(config: &ShortcutsConfig, shortcut: &str) -> bool {
+    let (Ok(binding), Some(pause)) = (parse_binding(shortcut), config.pause_shortcut()) else {
+        return false;
+    };
+    binding.leader.id() == pause.id()
+}
+
+/// Builds a conflict report covering every binding of every action, in registry order
+pub fn shortcut_status(config: &ShortcutsConfig, registrar: &impl ShortcutRegistrar) -> Vec<ShortcutStatus> {
+    let mut bindings: Vec<(&String, &String)> = config.bindings().collect();
+    bindings.sort_by_key(|(id, _)| {
+        registry::ACTIONS
+            .iter()
+            .position(|descriptor| descriptor.id == id.as_str())
+            .unwrap_or(usize::MAX)
+    });
+
+    bindings
+        .into_iter()
+        .map(|(id, shortcut)| {
+            let shortcut = shortcut.clone();
+            let mut conflicts = Vec::new();
+
+            let registered = match parse_binding(&shortcut) {
+                Ok(binding) => {
+                    let mut duplicates = find_duplicates(config, id, &shortcut);
+                    if is_window_mode_shortcut(config, &shortcut) {
+                        duplicates.push("windowMode".to_string());
+                    }
+                    if let Some(profile) = profile_shortcut(config, &shortcut) {
+                        duplicates.push(format!("profile:{}", profile));
+                    }
+                    if is_pause_shortcut(config, &shortcut) {
+                        duplicates.push("pause".to_string());
+                    }
+                    if !duplicates.is_empty() {
+                        conflicts.push(Conflict::Duplicate { with: duplicates });
+                    }
+                    if is_reserved(&shortcut) {
+                        conflicts.push(Conflict::Reserved);
+                    }
+
+                    let registered = registrar.is_registered(binding.leader);
+                    if !registered {
+                        conflicts.push(Conflict::RegistrationFailed);
+                    }
+                    registered
+                }
+                Err(error) => {
+                    conflicts.push(Conflict::Invalid { error });
+                    false
+                }
+            };
+
+            ShortcutStatus {
+                id: id.clone(),
+                shortcut,
+                registered,
+                conflicts,
+            }
+        })
+        .collect()
+}
+
+/// Sends the current conflict report to the frontend as a `shortcut-status` event
+pub fn emit_shortcut_status(app_handle: &AppHandle, config: &ShortcutsConfig) {
+    let status = shortcut_status(config, app_handle.global_shortcut());
+    let conflicts = status.iter().filter(|s| !s.conflicts.is_empty()).count();
+    if conflicts > 0 {
+        eprintln!("{} shortcuts have conflicts", conflicts);
+    }
+    let _ = app_handle.emit("shortcut-status", status);
+}
+
+/// Returns the conflict report for every configured shortcut
+#[tauri::command]
+pub async fn get_shortcut_status(
+    app_handle: AppHandle,
+    state: State<'_, ShortcutManager>,
+) -> Result<Vec<ShortcutStatus>, String> {
+    let config = snapshot(&state.shortcuts)?;
+    Ok(shortcut_status(&config, app_handle.global_shortcut()))
+}
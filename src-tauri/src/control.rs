@@ -0,0 +1,220 @@
+use serde::Serialize;
+use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
+use winapi::shared::windef::{HWND, RECT};
+use winapi::um::dwmapi::{DwmGetWindowAttribute, DWMWA_CLOAKED};
+use winapi::um::winuser::{
+    EnumWindows, GetForegroundWindow, GetWindowLongW, GetWindowRect, GetWindowTextLengthW, GetWindowTextW,
+    IsWindowVisible, GWL_EXSTYLE, WS_EX_TOOLWINDOW,
+};
+
+use crate::ipc::{Request, Response};
+use crate::profiles;
+use crate::registry;
+use crate::shortcuts::ShortcutsConfig;
+use crate::suspend;
+use crate::window_actions::geometry;
+use crate::window_layouts;
+use crate::Action;
+
+// Converts an action id to how it's written on the command line, e.g.
+// `leftHalf` to `left-half`
+fn kebab_case(id: &str) -> String {
+    let mut name = String::new();
+    for c in id.chars() {
+        if c.is_ascii_uppercase() {
+            name.push('-');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
+// Finds the action `name` refers to, by id or in kebab-case
+fn resolve(name: &str, monitor: Option<usize>) -> Result<Action, String> {
+    let descriptor = registry::ACTIONS
+        .iter()
+        .chain(registry::COMMAND_ACTIONS)
+        .find(|descriptor| descriptor.id == name || kebab_case(descriptor.id) == name)
+        .ok_or_else(|| {
+            let names: Vec<String> = registry::ACTIONS
+                .iter()
+                .chain(registry::COMMAND_ACTIONS)
+                .map(|descriptor| kebab_case(descriptor.id))
+                .collect();
+            format!("Unknown action: {} (expected one of {})", name, names.join(", "))
+        })?;
+
+    match descriptor.action {
+        Action::MoveToMonitor { .. } => {
+            let number = monitor.ok_or_else(|| format!("{} needs a monitor number, e.g. {} 2", name, name))?;
+            crate::get_monitor_info();
+            let count = geometry::numbered(&crate::MONITORS.lock().unwrap()).len();
+            if !(1..=count).contains(&number) {
+                return Err(format!("No monitor {} (1..={})", number, count));
+            }
+            Ok(Action::MoveToMonitor { number })
+        }
+        ref action => Ok(action.clone()),
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MonitorEntry {
+    /// Number to pass to `move-to-monitor`, counting from 1 on the left
+    number: usize,
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    work_left: i32,
+    work_top: i32,
+    work_right: i32,
+    work_bottom: i32,
+    dpi: u32,
+    scale_factor: f32,
+}
+
+fn list_monitors() -> Vec<MonitorEntry> {
+    crate::get_monitor_info();
+    let monitors = crate::MONITORS.lock().unwrap().clone();
+    geometry::numbered(&monitors)
+        .into_iter()
+        .enumerate()
+        .map(|(position, idx)| {
+            let monitor = &monitors[idx];
+            MonitorEntry {
+                number: position + 1,
+                left: monitor.left,
+                top: monitor.top,
+                right: monitor.right,
+                bottom: monitor.bottom,
+                work_left: monitor.work_left,
+                work_top: monitor.work_top,
+                work_right: monitor.work_right,
+                work_bottom: monitor.work_bottom,
+                dpi: monitor.dpi,
+                scale_factor: monitor.scale_factor,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowEntry {
+    /// Window handle
+    handle: usize,
+    title: String,
+    /// Executable name of the owning process
+    process: Option<String>,
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    /// Number of the monitor the window is on, see [`MonitorEntry::number`]
+    monitor: usize,
+    focused: bool,
+}
+
+unsafe extern "system" fn collect_window(hwnd: HWND, windows: LPARAM) -> BOOL {
+    let windows = &mut *(windows as *mut Vec<HWND>);
+    windows.push(hwnd);
+    TRUE
+}
+
+// Returns true for the windows the taskbar shows: visible, titled, not a
+// tool window and not cloaked on another virtual desktop
+unsafe fn is_app_window(hwnd: HWND) -> bool {
+    if IsWindowVisible(hwnd) == 0 || GetWindowTextLengthW(hwnd) == 0 {
+        return false;
+    }
+    if GetWindowLongW(hwnd, GWL_EXSTYLE) as u32 & WS_EX_TOOLWINDOW != 0 {
+        return false;
+    }
+    let mut cloaked: u32 = 0;
+    let result = DwmGetWindowAttribute(
+        hwnd,
+        DWMWA_CLOAKED,
+        &mut cloaked as *mut u32 as *mut _,
+        std::mem::size_of::<u32>() as u32,
+    );
+    result != 0 || cloaked == 0
+}
+
+/// Returns the application windows, topmost first
+pub fn app_windows() -> Vec<HWND> {
+    let mut handles: Vec<HWND> = Vec::new();
+    unsafe {
+        EnumWindows(Some(collect_window), &mut handles as *mut Vec<HWND> as LPARAM);
+        handles.retain(|hwnd| is_app_window(*hwnd));
+    }
+    handles
+}
+
+/// Returns the title of `hwnd`
+pub unsafe fn window_title(hwnd: HWND) -> String {
+    let mut title = vec![0u16; GetWindowTextLengthW(hwnd) as usize + 1];
+    let len = GetWindowTextW(hwnd, title.as_mut_ptr(), title.len() as i32);
+    String::from_utf16_lossy(&title[..len.max(0) as usize])
+}
+
+fn list_windows() -> Vec<WindowEntry> {
+    crate::get_monitor_info();
+    let monitors = crate::MONITORS.lock().unwrap().clone();
+    let numbers = geometry::numbered(&monitors);
+
+    unsafe {
+        let foreground = GetForegroundWindow();
+
+        app_windows()
+            .into_iter()
+            .filter_map(|hwnd| {
+                let mut rect: RECT = std::mem::zeroed();
+                if GetWindowRect(hwnd, &mut rect) == 0 {
+                    return None;
+                }
+                let monitor = geometry::current_monitor(rect.left, rect.top, rect.right, rect.bottom, &monitors);
+
+                Some(WindowEntry {
+                    handle: hwnd as usize,
+                    title: window_title(hwnd),
+                    process: suspend::process_name(hwnd),
+                    left: rect.left,
+                    top: rect.top,
+                    right: rect.right,
+                    bottom: rect.bottom,
+                    monitor: numbers
+                        .iter()
+                        .position(|idx| Some(*idx) == monitor)
+                        .map_or(0, |position| position + 1),
+                    focused: hwnd == foreground,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Carries out a command line request, in the running instance or in a
+/// one-shot process. Listings are returned as JSON.
+pub fn execute(request: &Request) -> Response {
+    match request {
+        Request::Action { name, monitor } => {
+            let action = resolve(name, *monitor)?;
+            crate::move_window(Some(action))?;
+            Ok(format!("Ran {}", name))
+        }
+        Request::Layout { name } => {
+            // Read from the settings file, which every change is saved to,
+            // without touching the running instance's profiles
+            let config = match ShortcutsConfig::read_settings().map_err(|e| e.to_string())? {
+                Some(file) => ShortcutsConfig::from_overrides(profiles::active_overrides(&file)),
+                None => ShortcutsConfig::default(),
+            };
+            window_layouts::apply(&config, name)
+        }
+        Request::Monitors => serde_json::to_string_pretty(&list_monitors()).map_err(|e| e.to_string()),
+        Request::Windows => serde_json::to_string_pretty(&list_windows()).map_err(|e| e.to_string()),
+        Request::Launch { .. } => Err("Only a running instance can take a launch request".to_string()),
+    }
+}
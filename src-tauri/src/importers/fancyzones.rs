@@ -0,0 +1,251 @@
+use serde_json::Value;
+
+use super::{ImportReport, ImportedBinding};
+
+// How far (as a fraction of the screen) a zone edge may be off and still match an action
+const TOLERANCE: f64 = 0.02;
+
+// A screen area as (left, top, right, bottom) fractions of the work area
+type Area = (f64, f64, f64, f64);
+
+// Screen areas covered by Galaxy's snapping actions
+const ACTION_AREAS: &[(&str, Area)] = &[
+    ("maximizeWindow", (0.0, 0.0, 1.0, 1.0)),
+    ("leftHalf", (0.0, 0.0, 0.5, 1.0)),
+    ("rightHalf", (0.5, 0.0, 1.0, 1.0)),
+    ("topHalf", (0.0, 0.0, 1.0, 0.5)),
+    ("bottomHalf", (0.0, 0.5, 1.0, 1.0)),
+    ("topLeft", (0.0, 0.0, 0.5, 0.5)),
+    ("topRight", (0.5, 0.0, 1.0, 0.5)),
+    ("bottomLeft", (0.0, 0.5, 0.5, 1.0)),
+    ("bottomRight", (0.5, 0.5, 1.0, 1.0)),
+    ("firstThird", (0.0, 0.0, 1.0 / 3.0, 1.0)),
+    ("centerThird", (1.0 / 3.0, 0.0, 2.0 / 3.0, 1.0)),
+    ("lastThird", (2.0 / 3.0, 0.0, 1.0, 1.0)),
+    ("firstTwoThirds", (0.0, 0.0, 2.0 / 3.0, 1.0)),
+    ("lastTwoThirds", (1.0 / 3.0, 0.0, 1.0, 1.0)),
+];
+
+/// Returns the Galaxy action snapping to `area`, if any
+fn matching_action(area: Area) -> Option<&'static str> {
+    let close = |a: f64, b: f64| (a - b).abs() <= TOLERANCE;
+    ACTION_AREAS
+        .iter()
+        .find(|(_, (left, top, right, bottom))| {
+            close(area.0, *left) && close(area.1, *top) && close(area.2, *right) && close(area.3, *bottom)
+        })
+        .map(|(id, _)| *id)
+}
+
+// Turns FancyZones percentages (in 1/10000ths) into cumulative edges between 0 and 1
+fn edges(percentages: &[Value]) -> Vec<f64> {
+    let mut edges = vec![0.0];
+    let mut total = 0.0;
+    for percentage in percentages {
+        total += percentage.as_f64().unwrap_or(0.0) / 10000.0;
+        edges.push(total);
+    }
+    edges
+}
+
+/// Computes the zones of a grid layout, in zone index order
+fn grid_zones(info: &Value) -> Result<Vec<Area>, String> {
+    let rows = edges(info["rows-percentage"].as_array().ok_or("Grid layout without rows")?);
+    let columns = edges(info["columns-percentage"].as_array().ok_or("Grid layout without columns")?);
+    let cells = info["cell-child-map"].as_array().ok_or("Grid layout without cell map")?;
+
+    // A zone spans every cell that carries its index
+    let mut zones: Vec<Option<Area>> = Vec::new();
+    for (row, cell_row) in cells.iter().enumerate() {
+        for (column, zone) in cell_row.as_array().into_iter().flatten().enumerate() {
+            let Some(zone) = zone.as_u64().map(|zone| zone as usize) else {
+                continue;
+            };
+            let (Some(&top), Some(&bottom), Some(&left), Some(&right)) =
+                (rows.get(row), rows.get(row + 1), columns.get(column), columns.get(column + 1))
+            else {
+                continue;
+            };
+
+            if zones.len() <= zone {
+                zones.resize(zone + 1, None);
+            }
+            zones[zone] = Some(match zones[zone] {
+                Some((l, t, r, b)) => (l.min(left), t.min(top), r.max(right), b.max(bottom)),
+                None => (left, top, right, bottom),
+            });
+        }
+    }
+
+    Ok(zones.into_iter().flatten().collect())
+}
+
+/// Computes the zones of a canvas layout, relative to its reference size
+fn canvas_zones(info: &Value) -> Result<Vec<Area>, String> {
+    let width = info["ref-width"].as_f64().filter(|w| *w > 0.0).ok_or("Canvas layout without width")?;
+    let height = info["ref-height"].as_f64().filter(|h| *h > 0.0).ok_or("Canvas layout without height")?;
+    let zones = info["zones"].as_array().ok_or("Canvas layout without zones")?;
+
+    Ok(zones
+        .iter()
+        .map(|zone| {
+            let x = zone["X"].as_f64().unwrap_or(0.0) / width;
+            let y = zone["Y"].as_f64().unwrap_or(0.0) / height;
+            let w = zone["width"].as_f64().unwrap_or(0.0) / width;
+            let h = zone["height"].as_f64().unwrap_or(0.0) / height;
+            (x, y, x + w, y + h)
+        })
+        .collect())
+}
+
+/// Maps the zones of the first custom FancyZones layout onto Galaxy actions.
+///
+/// FancyZones has no shortcut per zone, so each zone that matches a Galaxy
+/// action is bound to its number key in window mode: zone 1 to `1`, and so on.
+pub fn import(json: &Value) -> Result<ImportReport, String> {
+    let layouts = json
+        .get("custom-layouts")
+        .and_then(Value::as_array)
+        .ok_or("Not a FancyZones layout file: no \"custom-layouts\" array")?;
+    let (layout, skipped) = layouts.split_first().ok_or("The FancyZones layout file has no layouts")?;
+
+    let mut report = ImportReport::default();
+    let name = layout["name"].as_str().unwrap_or("unnamed");
+
+    let zones = match layout["type"].as_str() {
+        Some("grid") => grid_zones(&layout["info"])?,
+        Some("canvas") => canvas_zones(&layout["info"])?,
+        other => return Err(format!("Unsupported FancyZones layout type: {:?}", other)),
+    };
+
+    for (index, zone) in zones.into_iter().enumerate() {
+        let number = index + 1;
+        let source = format!("{} zone {}", name, number);
+
+        // Window mode only has the digit keys 1-9 to offer
+        if number > 9 {
+            report.unmapped.push(format!("{}: no number key left", source));
+            continue;
+        }
+        match matching_action(zone) {
+            Some(id) => report.window_mode_keys.push(ImportedBinding {
+                id: id.to_string(),
+                shortcut: number.to_string(),
+                source,
+            }),
+            None => report.unmapped.push(format!("{}: no Galaxy action covers this area", source)),
+        }
+    }
+
+    for layout in skipped {
+        report.unmapped.push(format!(
+            "{}: only the first layout is imported",
+            layout["name"].as_str().unwrap_or("unnamed")
+        ));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = include_str!("../../fixtures/fancyzones-grid.json");
+    const CANVAS: &str = include_str!("../../fixtures/fancyzones-canvas.json");
+
+    fn assert_areas(actual: &[Area], expected: &[Area]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (actual, expected) in actual.iter().zip(expected) {
+            let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+            assert!(
+                close(actual.0, expected.0) && close(actual.1, expected.1) && close(actual.2, expected.2) && close(actual.3, expected.3),
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    fn keys(report: &ImportReport) -> Vec<(&str, &str)> {
+        report
+            .window_mode_keys
+            .iter()
+            .map(|binding| (binding.shortcut.as_str(), binding.id.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn actions_match_within_the_tolerance() {
+        assert_eq!(matching_action((0.0, 0.0, 0.5, 1.0)), Some("leftHalf"));
+        assert_eq!(matching_action((0.01, 0.0, 0.49, 0.985)), Some("leftHalf"));
+        assert_eq!(matching_action((0.0, 0.0, 0.3333, 1.0)), Some("firstThird"));
+        assert_eq!(matching_action((0.0, 0.0, 0.53, 1.0)), None);
+        assert_eq!(matching_action((0.25, 0.25, 0.75, 0.75)), None);
+    }
+
+    #[test]
+    fn grid_zones_span_every_cell_they_own() {
+        let json: Value = serde_json::from_str(GRID).unwrap();
+        let zones = grid_zones(&json["custom-layouts"][0]["info"]).unwrap();
+        assert_areas(
+            &zones,
+            &[
+                // Zone 0 takes the first column of both rows, zone 1 the last two columns of the top row
+                (0.0, 0.0, 0.3333, 1.0),
+                (0.3333, 0.0, 1.0, 0.5),
+                (0.3333, 0.5, 0.6667, 1.0),
+                (0.6667, 0.5, 1.0, 1.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn grid_layout_is_imported_as_number_keys() {
+        let report = import(&serde_json::from_str(GRID).unwrap()).unwrap();
+        assert_eq!(keys(&report), [("1", "firstThird")]);
+        assert_eq!(
+            report.unmapped,
+            [
+                "Code and Docs zone 2: no Galaxy action covers this area",
+                "Code and Docs zone 3: no Galaxy action covers this area",
+                "Code and Docs zone 4: no Galaxy action covers this area",
+                "Presenting: only the first layout is imported",
+            ]
+        );
+        assert!(report.shortcuts.is_empty());
+    }
+
+    #[test]
+    fn canvas_zones_are_scaled_to_the_reference_size() {
+        let json: Value = serde_json::from_str(CANVAS).unwrap();
+        let zones = canvas_zones(&json["custom-layouts"][0]["info"]).unwrap();
+        assert_areas(&zones[..2], &[(0.0, 0.0, 0.5, 1.0), (0.5, 0.0, 1.0, 0.5)]);
+        assert_areas(&zones[3..], &[(0.25, 0.25, 0.75, 0.75)]);
+
+        // Zone 3 is a few pixels off the bottom right quarter, which still matches
+        let report = import(&json).unwrap();
+        assert_eq!(keys(&report), [("1", "leftHalf"), ("2", "topRight"), ("3", "bottomRight")]);
+        assert_eq!(report.unmapped, ["Ultrawide zone 4: no Galaxy action covers this area"]);
+    }
+
+    #[test]
+    fn only_nine_zones_get_a_key() {
+        let zones: Vec<Value> = (0..10)
+            .map(|_| serde_json::json!({ "X": 0, "Y": 0, "width": 100, "height": 100 }))
+            .collect();
+        let json = serde_json::json!({
+            "custom-layouts": [
+                { "name": "Stacked", "type": "canvas", "info": { "ref-width": 100, "ref-height": 100, "zones": zones } }
+            ]
+        });
+        let report = import(&json).unwrap();
+        assert_eq!(report.window_mode_keys.len(), 9);
+        assert_eq!(report.unmapped, ["Stacked zone 10: no number key left"]);
+    }
+
+    #[test]
+    fn other_files_are_rejected() {
+        assert!(import(&serde_json::json!({ "shortcuts": {} })).is_err());
+        assert!(import(&serde_json::json!({ "custom-layouts": [] })).is_err());
+        assert!(import(&serde_json::json!({ "custom-layouts": [{ "type": "priority-grid" }] })).is_err());
+    }
+}
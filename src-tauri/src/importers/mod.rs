@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+use crate::conflicts;
+use crate::shortcuts::{self, ShortcutManager, ShortcutsConfig};
+
+mod fancyzones;
+mod rectangle;
+
+/// Window managers whose settings can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    /// Rectangle's exported `RectangleConfig.json` (macOS)
+    Rectangle,
+    /// PowerToys FancyZones' `custom-layouts.json`
+    FancyZones,
+}
+
+impl std::str::FromStr for ImportSource {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source.to_lowercase().as_str() {
+            "rectangle" => Ok(ImportSource::Rectangle),
+            "fancyzones" => Ok(ImportSource::FancyZones),
+            _ => Err(format!("Unknown import source: {} (expected rectangle or fancyzones)", source)),
+        }
+    }
+}
+
+/// A binding taken over from another window manager
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedBinding {
+    /// Galaxy action id
+    pub id: String,
+    /// Galaxy shortcut string; a plain key for window mode keys
+    pub shortcut: String,
+    /// What it was called in the imported file
+    pub source: String,
+}
+
+/// What an import did, and what it couldn't map
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// Action shortcuts that were imported
+    pub shortcuts: Vec<ImportedBinding>,
+    /// Window mode keys that were imported
+    pub window_mode_keys: Vec<ImportedBinding>,
+    /// Entries of the imported file that have no Galaxy equivalent
+    pub unmapped: Vec<String>,
+    /// Imported shortcuts that collide with existing Galaxy shortcuts or are
+    /// reserved by Windows
+    pub conflicts: Vec<String>,
+}
+
+/// Reads `path` and applies what maps onto Galaxy to `config`.
+///
+/// Imported shortcuts replace the bindings of the matching actions, and
+/// imported window mode keys replace the keys they're bound to.
+pub fn import_into(config: &mut ShortcutsConfig, source: ImportSource, path: &Path) -> Result<ImportReport, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+
+    let report = match source {
+        ImportSource::Rectangle => rectangle::import(&json)?,
+        ImportSource::FancyZones => fancyzones::import(&json)?,
+    };
+    Ok(apply(config, report))
+}
+
+// Applies an import report to `config`. Imported shortcuts are written in
+// canonical form; the ones whose action isn't in the settings are moved to
+// `unmapped`, and the ones Windows reserves to `conflicts`.
+fn apply(config: &mut ShortcutsConfig, mut report: ImportReport) -> ImportReport {
+    let mut imported = Vec::new();
+    for mut binding in std::mem::take(&mut report.shortcuts) {
+        if !config.shortcuts.contains_key(&binding.id) {
+            report.unmapped.push(format!("{}: action {} isn't in the settings", binding.source, binding.id));
+            continue;
+        }
+        match shortcuts::canonical_binding(&binding.shortcut) {
+            Ok(canonical) => binding.shortcut = canonical,
+            Err(e) => {
+                report.unmapped.push(format!("{}: {} ({})", binding.source, binding.shortcut, e));
+                continue;
+            }
+        }
+        if conflicts::is_reserved(&binding.shortcut) {
+            report.conflicts.push(format!(
+                "{} ({}) is reserved by Windows and wasn't imported",
+                binding.shortcut, binding.id
+            ));
+            continue;
+        }
+        imported.push(binding);
+    }
+    report.shortcuts = imported;
+
+    for binding in &mut report.window_mode_keys {
+        if let Ok(canonical) = shortcuts::canonical_binding(&binding.shortcut) {
+            binding.shortcut = canonical;
+        }
+    }
+
+    for binding in &report.shortcuts {
+        if let Some(shortcut_cfg) = config.shortcuts.get_mut(&binding.id) {
+            shortcut_cfg.bindings = vec![binding.shortcut.clone()];
+        }
+    }
+    for binding in &report.window_mode_keys {
+        config.window_mode.keys.insert(binding.shortcut.clone(), binding.id.clone());
+    }
+
+    for binding in &report.shortcuts {
+        let duplicates = conflicts::find_duplicates(config, &binding.id, &binding.shortcut);
+        if !duplicates.is_empty() {
+            report.conflicts.push(format!(
+                "{} ({}) is also used by {}",
+                binding.shortcut,
+                binding.id,
+                duplicates.join(", ")
+            ));
+        }
+    }
+
+    println!(
+        "Imported {} shortcuts and {} window mode keys, {} entries couldn't be mapped",
+        report.shortcuts.len(),
+        report.window_mode_keys.len(),
+        report.unmapped.len()
+    );
+    report
+}
+
+/// Imports the settings of another window manager into the active profile.
+///
+/// The new shortcuts are registered and saved as a single transaction.
+#[tauri::command]
+pub async fn import_settings(
+    app_handle: AppHandle,
+    source: ImportSource,
+    path: String,
+    state: State<'_, ShortcutManager>,
+) -> Result<ImportReport, String> {
+    let mut new_config = state.shortcuts.lock().map_err(|e| e.to_string())?.clone();
+    let report = import_into(&mut new_config, source, Path::new(&path))?;
+
+    shortcuts::replace_config(&app_handle, &state.shortcuts, new_config, ShortcutsConfig::save)?;
+    Ok(report)
+}
+
+/// Imports into the settings file without starting the app, for the
+/// `--import` command line flag. A running instance picks the change up
+/// through hot reload.
+pub fn import_to_file(source: ImportSource, path: &Path) -> Result<ImportReport, String> {
+    let mut config = ShortcutsConfig::load().map_err(|e| e.to_string())?;
+    let report = import_into(&mut config, source, path)?;
+    config.save()?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(id: &str, shortcut: &str) -> ImportedBinding {
+        ImportedBinding {
+            id: id.to_string(),
+            shortcut: shortcut.to_string(),
+            source: id.to_string(),
+        }
+    }
+
+    fn report(shortcuts: Vec<ImportedBinding>) -> ImportReport {
+        ImportReport {
+            shortcuts,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn imported_shortcuts_are_stored_in_canonical_form() {
+        let mut config = ShortcutsConfig::default();
+        let report = apply(&mut config, report(vec![binding("makeLarger", "Shift+Control+Alt+Equal")]));
+
+        assert_eq!(report.shortcuts[0].shortcut, "Control+Alt+Shift+Equal");
+        assert_eq!(config.shortcuts["makeLarger"].bindings, ["Control+Alt+Shift+Equal"]);
+    }
+
+    #[test]
+    fn shortcuts_for_missing_actions_are_unmapped() {
+        let mut config = ShortcutsConfig::default();
+        config.shortcuts.remove("center");
+        let report = apply(&mut config, report(vec![binding("center", "Control+Alt+C")]));
+
+        assert!(report.shortcuts.is_empty());
+        assert_eq!(report.unmapped, ["center: action center isn't in the settings"]);
+        assert!(!config.shortcuts.contains_key("center"));
+    }
+
+    #[test]
+    fn reserved_shortcuts_are_reported_and_left_out() {
+        let mut config = ShortcutsConfig::default();
+        let before = config.shortcuts["leftHalf"].bindings.clone();
+        let report = apply(&mut config, report(vec![binding("leftHalf", "Super+ArrowLeft")]));
+
+        assert!(report.shortcuts.is_empty());
+        assert_eq!(report.conflicts, ["Super+ArrowLeft (leftHalf) is reserved by Windows and wasn't imported"]);
+        assert_eq!(config.shortcuts["leftHalf"].bindings, before);
+    }
+
+    #[test]
+    fn shortcuts_taken_by_other_actions_are_reported() {
+        let mut config = ShortcutsConfig::default();
+        let taken = config.shortcuts["rightHalf"].bindings[0].clone();
+        let report = apply(&mut config, report(vec![binding("leftHalf", &taken)]));
+
+        assert_eq!(config.shortcuts["leftHalf"].bindings, [taken.as_str()]);
+        assert_eq!(report.conflicts, [format!("{} (leftHalf) is also used by rightHalf", taken)]);
+    }
+}
@@ -0,0 +1,248 @@
+use serde_json::Value;
+
+use super::{ImportReport, ImportedBinding};
+
+// NSEvent modifier flags used in Rectangle's `modifierFlags`
+const SHIFT_FLAG: u64 = 1 << 17;
+const CONTROL_FLAG: u64 = 1 << 18;
+const OPTION_FLAG: u64 = 1 << 19;
+const COMMAND_FLAG: u64 = 1 << 20;
+
+// Rectangle action names and the Galaxy actions they correspond to
+const ACTION_MAP: &[(&str, &str)] = &[
+    ("leftHalf", "leftHalf"),
+    ("rightHalf", "rightHalf"),
+    ("topHalf", "topHalf"),
+    ("bottomHalf", "bottomHalf"),
+    ("topLeft", "topLeft"),
+    ("topRight", "topRight"),
+    ("bottomLeft", "bottomLeft"),
+    ("bottomRight", "bottomRight"),
+    ("firstThird", "firstThird"),
+    ("centerThird", "centerThird"),
+    ("lastThird", "lastThird"),
+    ("firstTwoThirds", "firstTwoThirds"),
+    ("lastTwoThirds", "lastTwoThirds"),
+    ("maximize", "maximizeWindow"),
+    ("almostMaximize", "almostMaximizeWindow"),
+    ("maximizeHeight", "maximizeHeight"),
+    ("center", "center"),
+    ("larger", "makeLarger"),
+    ("smaller", "makeSmaller"),
+    ("previousDisplay", "moveMonitorLeft"),
+    ("nextDisplay", "moveMonitorRight"),
+];
+
+/// Maps a macOS virtual key code, which is a physical key position, to a Galaxy key name
+fn key_name(key_code: u64) -> Option<&'static str> {
+    let name = match key_code {
+        0 => "A",
+        1 => "S",
+        2 => "D",
+        3 => "F",
+        4 => "H",
+        5 => "G",
+        6 => "Z",
+        7 => "X",
+        8 => "C",
+        9 => "V",
+        11 => "B",
+        12 => "Q",
+        13 => "W",
+        14 => "E",
+        15 => "R",
+        16 => "Y",
+        17 => "T",
+        18 => "1",
+        19 => "2",
+        20 => "3",
+        21 => "4",
+        22 => "6",
+        23 => "5",
+        24 => "Equal",
+        25 => "9",
+        26 => "7",
+        27 => "Minus",
+        28 => "8",
+        29 => "0",
+        30 => "BracketRight",
+        31 => "O",
+        32 => "U",
+        33 => "BracketLeft",
+        34 => "I",
+        35 => "P",
+        36 => "Enter",
+        37 => "L",
+        38 => "J",
+        39 => "Quote",
+        40 => "K",
+        41 => "Semicolon",
+        42 => "Backslash",
+        43 => "Comma",
+        44 => "Slash",
+        45 => "N",
+        46 => "M",
+        47 => "Period",
+        48 => "Tab",
+        49 => "Space",
+        50 => "Backquote",
+        51 => "Backspace",
+        53 => "Escape",
+        65 => "NumpadDecimal",
+        67 => "NumpadMultiply",
+        69 => "NumpadAdd",
+        75 => "NumpadDivide",
+        76 => "NumpadEnter",
+        78 => "NumpadSubtract",
+        82 => "Numpad0",
+        83 => "Numpad1",
+        84 => "Numpad2",
+        85 => "Numpad3",
+        86 => "Numpad4",
+        87 => "Numpad5",
+        88 => "Numpad6",
+        89 => "Numpad7",
+        91 => "Numpad8",
+        92 => "Numpad9",
+        96 => "F5",
+        97 => "F6",
+        98 => "F7",
+        99 => "F3",
+        100 => "F8",
+        101 => "F9",
+        103 => "F11",
+        109 => "F10",
+        111 => "F12",
+        115 => "Home",
+        116 => "PageUp",
+        117 => "Delete",
+        118 => "F4",
+        119 => "End",
+        120 => "F2",
+        121 => "PageDown",
+        122 => "F1",
+        123 => "ArrowLeft",
+        124 => "ArrowRight",
+        125 => "ArrowDown",
+        126 => "ArrowUp",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Converts Rectangle modifier flags to Galaxy modifiers: ⌃ becomes Control,
+/// ⌥ becomes Alt and ⌘ becomes the Windows key, which sits in its place on
+/// PC keyboards.
+fn modifiers(flags: u64) -> Vec<&'static str> {
+    let mut modifiers = Vec::new();
+    if flags & SHIFT_FLAG != 0 {
+        modifiers.push("Shift");
+    }
+    if flags & CONTROL_FLAG != 0 {
+        modifiers.push("Control");
+    }
+    if flags & OPTION_FLAG != 0 {
+        modifiers.push("Alt");
+    }
+    if flags & COMMAND_FLAG != 0 {
+        modifiers.push("Super");
+    }
+    modifiers
+}
+
+/// Maps the `shortcuts` of a Rectangle export onto Galaxy actions
+pub fn import(json: &Value) -> Result<ImportReport, String> {
+    let shortcuts = json
+        .get("shortcuts")
+        .and_then(Value::as_object)
+        .ok_or("Not a Rectangle export: no \"shortcuts\" object")?;
+
+    let mut report = ImportReport::default();
+
+    for (name, shortcut) in shortcuts {
+        // Rectangle keeps an empty entry for actions without a shortcut
+        let Some(key_code) = shortcut.get("keyCode").and_then(Value::as_u64) else {
+            continue;
+        };
+        let flags = shortcut.get("modifierFlags").and_then(Value::as_u64).unwrap_or(0);
+
+        let Some((_, id)) = ACTION_MAP.iter().find(|(rectangle, _)| rectangle == name) else {
+            report.unmapped.push(format!("{}: no matching Galaxy action", name));
+            continue;
+        };
+        let Some(key) = key_name(key_code) else {
+            report.unmapped.push(format!("{}: unknown key code {}", name, key_code));
+            continue;
+        };
+
+        let mut parts = modifiers(flags);
+        parts.push(key);
+        report.shortcuts.push(ImportedBinding {
+            id: id.to_string(),
+            shortcut: parts.join("+"),
+            source: name.clone(),
+        });
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = include_str!("../../fixtures/rectangle-config.json");
+
+    #[test]
+    fn key_codes_are_physical_keys() {
+        assert_eq!(key_name(0), Some("A"));
+        assert_eq!(key_name(22), Some("6"));
+        assert_eq!(key_name(33), Some("BracketLeft"));
+        assert_eq!(key_name(50), Some("Backquote"));
+        assert_eq!(key_name(84), Some("Numpad2"));
+        assert_eq!(key_name(123), Some("ArrowLeft"));
+        assert_eq!(key_name(10), None);
+    }
+
+    #[test]
+    fn modifier_flags_map_to_pc_modifiers() {
+        assert_eq!(modifiers(786432), ["Control", "Alt"]);
+        assert_eq!(modifiers(SHIFT_FLAG | COMMAND_FLAG), ["Shift", "Super"]);
+        assert_eq!(modifiers(SHIFT_FLAG | CONTROL_FLAG | OPTION_FLAG | COMMAND_FLAG), ["Shift", "Control", "Alt", "Super"]);
+        // Caps lock and the other device-dependent bits are ignored
+        assert!(modifiers((1 << 16) | 0xff).is_empty());
+    }
+
+    #[test]
+    fn export_is_mapped_onto_galaxy_actions() {
+        let report = import(&serde_json::from_str(EXPORT).unwrap()).unwrap();
+
+        let shortcuts: Vec<(&str, &str, &str)> = report
+            .shortcuts
+            .iter()
+            .map(|binding| (binding.source.as_str(), binding.id.as_str(), binding.shortcut.as_str()))
+            .collect();
+        assert_eq!(
+            shortcuts,
+            [
+                ("bottomHalf", "bottomHalf", "Super+Numpad2"),
+                ("larger", "makeLarger", "Shift+Control+Alt+Equal"),
+                ("leftHalf", "leftHalf", "Control+Alt+ArrowLeft"),
+                ("maximize", "maximizeWindow", "Control+Alt+Enter"),
+                ("nextDisplay", "moveMonitorRight", "Control+Alt+Super+ArrowRight"),
+                ("rightHalf", "rightHalf", "Control+Alt+ArrowRight"),
+                ("topLeft", "topLeft", "Control+Alt+U"),
+            ]
+        );
+        assert_eq!(
+            report.unmapped,
+            ["center: unknown key code 200", "restore: no matching Galaxy action"]
+        );
+        assert!(report.window_mode_keys.is_empty());
+    }
+
+    #[test]
+    fn other_files_are_rejected() {
+        assert!(import(&serde_json::json!({ "custom-layouts": [] })).is_err());
+    }
+}
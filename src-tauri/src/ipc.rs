@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
+use std::ptr::null_mut;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use winapi::shared::winerror::{ERROR_FILE_NOT_FOUND, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED};
+use winapi::um::errhandlingapi::GetLastError;
+use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+use winapi::um::namedpipeapi::{ConnectNamedPipe, CreateNamedPipeW};
+use winapi::um::winbase::{
+    FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX, PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS,
+    PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+};
+
+use crate::control;
+use crate::pause;
+use crate::profiles;
+use crate::shortcuts::ShortcutManager;
+
+// How often and how long a client retries while the running instance is
+// busy answering another one
+const BUSY_RETRIES: u32 = 20;
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// What a later launch asks the running instance to do
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "request", rename_all = "camelCase")]
+pub enum Request {
+    /// Galaxy was launched again. Switches profile and pauses as asked, or
+    /// opens the settings when asked for nothing else.
+    Launch {
+        profile: Option<String>,
+        pause: Option<u64>,
+    },
+    /// Runs an action on the focused window, named by its id or in
+    /// kebab-case, e.g. `left-half`. `monitor` is the number for `move-to-monitor`.
+    Action { name: String, monitor: Option<usize> },
+    /// Applies the window layout `name` of the active profile
+    Layout { name: String },
+    /// Lists the monitors as JSON
+    Monitors,
+    /// Lists the application windows as JSON
+    Windows,
+}
+
+/// The running instance's answer: a message to print, or an error
+pub type Response = Result<String, String>;
+
+// The pipe is per user, so several people signed in to the same machine each
+// get their own Galaxy
+fn pipe_name() -> String {
+    let user = std::env::var("USERNAME").unwrap_or_default();
+    format!(r"\\.\pipe\galaxy-{}", user)
+}
+
+// Creates a listening instance of the pipe. The first one fails if another
+// process already owns the pipe, which is what makes Galaxy single-instance.
+fn create_pipe(first: bool) -> std::io::Result<OwnedHandle> {
+    let name: Vec<u16> = std::ffi::OsStr::new(&pipe_name()).encode_wide().chain(Some(0)).collect();
+    let open_mode = if first {
+        PIPE_ACCESS_DUPLEX | FILE_FLAG_FIRST_PIPE_INSTANCE
+    } else {
+        PIPE_ACCESS_DUPLEX
+    };
+    let handle = unsafe {
+        CreateNamedPipeW(
+            name.as_ptr(),
+            open_mode,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            PIPE_UNLIMITED_INSTANCES,
+            4096,
+            4096,
+            0,
+            null_mut(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(unsafe { OwnedHandle::from_raw_handle(handle as _) })
+}
+
+/// Claims the pipe for this process. Fails when another instance is
+/// running, in which case this one should exit.
+pub fn claim() -> Result<OwnedHandle, String> {
+    create_pipe(true).map_err(|e| format!("Galaxy is already running ({})", e))
+}
+
+// Waits for a client on a listening pipe instance
+fn accept(pipe: OwnedHandle) -> std::io::Result<File> {
+    let connected = unsafe { ConnectNamedPipe(pipe.as_raw_handle() as _, null_mut()) };
+    // A client connecting between creation and this call is reported as an error
+    if connected == 0 && unsafe { GetLastError() } != ERROR_PIPE_CONNECTED {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(File::from(pipe))
+}
+
+/// Sends `request` to the running instance and returns its response, or
+/// `None` if no instance is running
+pub fn send(request: &Request) -> Option<Response> {
+    let mut pipe = None;
+    for _ in 0..BUSY_RETRIES {
+        match OpenOptions::new().read(true).write(true).open(pipe_name()) {
+            Ok(file) => {
+                pipe = Some(file);
+                break;
+            }
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY as i32) => std::thread::sleep(BUSY_RETRY_DELAY),
+            Err(e) if e.raw_os_error() == Some(ERROR_FILE_NOT_FOUND as i32) => return None,
+            Err(e) => return Some(Err(format!("Failed to reach the running instance: {}", e))),
+        }
+    }
+    let Some(pipe) = pipe else {
+        return Some(Err("The running instance is not responding".to_string()));
+    };
+    Some(exchange(&pipe, request).unwrap_or_else(Err))
+}
+
+// Writes a request to the pipe and reads the response
+fn exchange(mut pipe: &File, request: &Request) -> Result<Response, String> {
+    let mut line = serde_json::to_string(request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    pipe.write_all(line.as_bytes()).map_err(|e| e.to_string())?;
+
+    let mut answer = String::new();
+    BufReader::new(pipe).read_line(&mut answer).map_err(|e| e.to_string())?;
+    serde_json::from_str(&answer).map_err(|e| format!("Invalid response from the running instance: {}", e))
+}
+
+// Carries out a request from another process
+fn handle(app_handle: &AppHandle, request: Request) -> Response {
+    println!("Received request from another instance: {:?}", request);
+    match request {
+        Request::Launch { profile, pause } => {
+            let shortcuts_state = app_handle.state::<ShortcutManager>().shortcuts.clone();
+            let mut done = Vec::new();
+
+            if let Some(name) = profile {
+                profiles::switch_profile(app_handle, &shortcuts_state, &name)?;
+                done.push(format!("switched to profile '{}'", name));
+            }
+            if let Some(minutes) = pause {
+                let duration = (minutes > 0).then(|| Duration::from_secs(minutes * 60));
+                pause::pause(app_handle, &shortcuts_state, duration)?;
+                done.push("paused shortcuts".to_string());
+            }
+            if done.is_empty() {
+                if let Some(window) = app_handle.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+                done.push("opened the settings".to_string());
+            }
+            Ok(format!("Galaxy is already running: {}", done.join(", ")))
+        }
+        request => control::execute(&request),
+    }
+}
+
+// Reads one request from a connected client and writes back the response
+fn serve_client(app_handle: &AppHandle, pipe: File) -> std::io::Result<()> {
+    let mut line = String::new();
+    BufReader::new(&pipe).read_line(&mut line)?;
+    let response = match serde_json::from_str::<Request>(&line) {
+        Ok(request) => handle(app_handle, request),
+        Err(e) => Err(format!("Invalid request: {}", e)),
+    };
+
+    let mut answer = serde_json::to_string(&response).map_err(std::io::Error::other)?;
+    answer.push('\n');
+    (&pipe).write_all(answer.as_bytes())?;
+    // Closing the pipe before the client read the answer would discard it
+    pipe.sync_all()
+}
+
+/// Answers requests from later launches on the pipe claimed with [`claim`]
+pub fn serve(app_handle: AppHandle, pipe: OwnedHandle) {
+    std::thread::spawn(move || {
+        let mut listening = pipe;
+        loop {
+            let client = match accept(listening) {
+                Ok(client) => client,
+                Err(e) => {
+                    eprintln!("Failed to accept a connection from another instance: {}", e);
+                    return;
+                }
+            };
+            // Keep an instance listening while this client is served, so a
+            // launch in the meantime never finds the pipe missing
+            listening = match create_pipe(false) {
+                Ok(pipe) => pipe,
+                Err(e) => {
+                    eprintln!("Failed to listen for other instances: {}", e);
+                    return;
+                }
+            };
+            // Each client gets its own thread, so one that never sends its
+            // request can't hold up the others
+            let app_handle = app_handle.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = serve_client(&app_handle, client) {
+                    eprintln!("Failed to answer another instance: {}", e);
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,414 @@
+use std::fmt;
+use std::str::FromStr;
+use tauri_plugin_global_shortcut::{Code, Modifiers, Shortcut};
+
+use crate::layout;
+
+// Modifiers in the order they're written, with the other names they're accepted under
+const MODIFIERS: &[(Modifiers, &str, &[&str])] = &[
+    (Modifiers::CONTROL, "Control", &["Ctrl", "Ctl", "⌃"]),
+    (Modifiers::ALT, "Alt", &["Option", "Opt", "⌥"]),
+    (Modifiers::SHIFT, "Shift", &["⇧"]),
+    (Modifiers::SUPER, "Super", &["Win", "Windows", "Meta", "Cmd", "Command", "⌘"]),
+];
+
+// Supported keys with their canonical name and aliases. Every key is also
+// accepted under its W3C code name, e.g. `KeyA` or `Digit1`.
+const KEYS: &[(Code, &str, &[&str])] = &[
+    (Code::KeyA, "A", &[]),
+    (Code::KeyB, "B", &[]),
+    (Code::KeyC, "C", &[]),
+    (Code::KeyD, "D", &[]),
+    (Code::KeyE, "E", &[]),
+    (Code::KeyF, "F", &[]),
+    (Code::KeyG, "G", &[]),
+    (Code::KeyH, "H", &[]),
+    (Code::KeyI, "I", &[]),
+    (Code::KeyJ, "J", &[]),
+    (Code::KeyK, "K", &[]),
+    (Code::KeyL, "L", &[]),
+    (Code::KeyM, "M", &[]),
+    (Code::KeyN, "N", &[]),
+    (Code::KeyO, "O", &[]),
+    (Code::KeyP, "P", &[]),
+    (Code::KeyQ, "Q", &[]),
+    (Code::KeyR, "R", &[]),
+    (Code::KeyS, "S", &[]),
+    (Code::KeyT, "T", &[]),
+    (Code::KeyU, "U", &[]),
+    (Code::KeyV, "V", &[]),
+    (Code::KeyW, "W", &[]),
+    (Code::KeyX, "X", &[]),
+    (Code::KeyY, "Y", &[]),
+    (Code::KeyZ, "Z", &[]),
+    (Code::Digit0, "0", &[]),
+    (Code::Digit1, "1", &[]),
+    (Code::Digit2, "2", &[]),
+    (Code::Digit3, "3", &[]),
+    (Code::Digit4, "4", &[]),
+    (Code::Digit5, "5", &[]),
+    (Code::Digit6, "6", &[]),
+    (Code::Digit7, "7", &[]),
+    (Code::Digit8, "8", &[]),
+    (Code::Digit9, "9", &[]),
+    (Code::Minus, "Minus", &["-", "Dash", "Hyphen"]),
+    (Code::Equal, "Equal", &["=", "Equals"]),
+    (Code::BracketLeft, "BracketLeft", &["["]),
+    (Code::BracketRight, "BracketRight", &["]"]),
+    (Code::Semicolon, "Semicolon", &[";"]),
+    (Code::Quote, "Quote", &["'", "Apostrophe"]),
+    (Code::Comma, "Comma", &[","]),
+    (Code::Period, "Period", &[".", "Dot"]),
+    (Code::Slash, "Slash", &["/"]),
+    (Code::Backslash, "Backslash", &["\\"]),
+    (Code::Backquote, "Backquote", &["`", "Grave", "Backtick"]),
+    (Code::ArrowLeft, "ArrowLeft", &["Left", "←"]),
+    (Code::ArrowRight, "ArrowRight", &["Right", "→"]),
+    (Code::ArrowUp, "ArrowUp", &["Up", "↑"]),
+    (Code::ArrowDown, "ArrowDown", &["Down", "↓"]),
+    (Code::Enter, "Enter", &["Return", "↵", "⏎"]),
+    (Code::Space, "Space", &["Spacebar"]),
+    (Code::Tab, "Tab", &["⇥"]),
+    (Code::Backspace, "Backspace", &["⌫"]),
+    (Code::Delete, "Delete", &["Del", "⌦"]),
+    (Code::Insert, "Insert", &["Ins"]),
+    (Code::Home, "Home", &[]),
+    (Code::End, "End", &[]),
+    (Code::PageUp, "PageUp", &["PgUp"]),
+    (Code::PageDown, "PageDown", &["PgDn"]),
+    (Code::Escape, "Escape", &["Esc", "⎋"]),
+    (Code::CapsLock, "CapsLock", &[]),
+    (Code::NumLock, "NumLock", &[]),
+    (Code::ScrollLock, "ScrollLock", &[]),
+    (Code::PrintScreen, "PrintScreen", &["PrtSc"]),
+    (Code::Pause, "Pause", &["Break"]),
+    (Code::Numpad0, "Numpad0", &["Num0"]),
+    (Code::Numpad1, "Numpad1", &["Num1"]),
+    (Code::Numpad2, "Numpad2", &["Num2"]),
+    (Code::Numpad3, "Numpad3", &["Num3"]),
+    (Code::Numpad4, "Numpad4", &["Num4"]),
+    (Code::Numpad5, "Numpad5", &["Num5"]),
+    (Code::Numpad6, "Numpad6", &["Num6"]),
+    (Code::Numpad7, "Numpad7", &["Num7"]),
+    (Code::Numpad8, "Numpad8", &["Num8"]),
+    (Code::Numpad9, "Numpad9", &["Num9"]),
+    (Code::NumpadAdd, "NumpadAdd", &["NumpadPlus", "NumAdd"]),
+    (Code::NumpadSubtract, "NumpadSubtract", &["NumpadMinus", "NumSubtract"]),
+    (Code::NumpadMultiply, "NumpadMultiply", &["NumMultiply"]),
+    (Code::NumpadDivide, "NumpadDivide", &["NumDivide"]),
+    (Code::NumpadDecimal, "NumpadDecimal", &["NumDecimal"]),
+    (Code::NumpadEnter, "NumpadEnter", &["NumEnter"]),
+    (Code::F1, "F1", &[]),
+    (Code::F2, "F2", &[]),
+    (Code::F3, "F3", &[]),
+    (Code::F4, "F4", &[]),
+    (Code::F5, "F5", &[]),
+    (Code::F6, "F6", &[]),
+    (Code::F7, "F7", &[]),
+    (Code::F8, "F8", &[]),
+    (Code::F9, "F9", &[]),
+    (Code::F10, "F10", &[]),
+    (Code::F11, "F11", &[]),
+    (Code::F12, "F12", &[]),
+    (Code::F13, "F13", &[]),
+    (Code::F14, "F14", &[]),
+    (Code::F15, "F15", &[]),
+    (Code::F16, "F16", &[]),
+    (Code::F17, "F17", &[]),
+    (Code::F18, "F18", &[]),
+    (Code::F19, "F19", &[]),
+    (Code::F20, "F20", &[]),
+    (Code::F21, "F21", &[]),
+    (Code::F22, "F22", &[]),
+    (Code::F23, "F23", &[]),
+    (Code::F24, "F24", &[]),
+    (Code::AudioVolumeMute, "AudioVolumeMute", &["VolumeMute"]),
+    (Code::AudioVolumeDown, "AudioVolumeDown", &["VolumeDown"]),
+    (Code::AudioVolumeUp, "AudioVolumeUp", &["VolumeUp"]),
+    (Code::MediaPlayPause, "MediaPlayPause", &[]),
+    (Code::MediaStop, "MediaStop", &[]),
+    (Code::MediaTrackNext, "MediaTrackNext", &[]),
+    (Code::MediaTrackPrevious, "MediaTrackPrevious", &["MediaTrackPrev"]),
+];
+
+/// One key combination: any number of modifiers plus exactly one key.
+///
+/// The key is a physical key, named after its position on a US keyboard.
+/// Parsed from strings such as `Ctrl+Alt+-` and written back in canonical
+/// form, here `Control+Alt+Minus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub modifiers: Modifiers,
+    pub key: Code,
+}
+
+/// Why a shortcut string couldn't be parsed, and which token is to blame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChordError {
+    pub input: String,
+    pub token: String,
+    /// Character offset of the token in the input, starting at 1
+    pub position: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for KeyChordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid shortcut '{}': {} at position {}",
+            self.input, self.reason, self.position
+        )
+    }
+}
+
+impl std::error::Error for KeyChordError {}
+
+// Looks up a modifier by name, ignoring case
+fn find_modifier(token: &str) -> Option<Modifiers> {
+    MODIFIERS
+        .iter()
+        .find(|(_, name, aliases)| {
+            name.eq_ignore_ascii_case(token) || aliases.iter().any(|alias| alias.eq_ignore_ascii_case(token))
+        })
+        .map(|(modifier, _, _)| *modifier)
+}
+
+// Looks up a key by canonical name, alias or W3C code name, ignoring case
+fn find_key(token: &str) -> Option<Code> {
+    KEYS.iter()
+        .find(|(code, name, aliases)| {
+            name.eq_ignore_ascii_case(token)
+                || aliases.iter().any(|alias| alias.eq_ignore_ascii_case(token))
+                || code.to_string().eq_ignore_ascii_case(token)
+        })
+        .map(|(code, _, _)| *code)
+}
+
+/// Returns the canonical name of a key
+pub fn key_name(key: Code) -> String {
+    KEYS.iter()
+        .find(|(code, _, _)| *code == key)
+        .map(|(_, name, _)| name.to_string())
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Returns the canonical names of the modifiers set in `modifiers`, in order
+pub fn modifier_names(modifiers: Modifiers) -> Vec<&'static str> {
+    MODIFIERS
+        .iter()
+        .filter(|(modifier, _, _)| modifiers.contains(*modifier))
+        .map(|(_, name, _)| *name)
+        .collect()
+}
+
+impl KeyChord {
+    /// Parses a shortcut written as `Modifier+Modifier+Key`
+    pub fn parse(input: &str) -> Result<Self, KeyChordError> {
+        let error = |token: &str, position: usize, reason: String| KeyChordError {
+            input: input.to_string(),
+            token: token.to_string(),
+            position,
+            reason,
+        };
+
+        // Split on '+', remembering where each token starts
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        for (index, part) in input.split('+').enumerate() {
+            if index > 0 {
+                start += 1;
+            }
+            let trimmed = part.trim_start();
+            let position = start + (part.chars().count() - trimmed.chars().count()) + 1;
+            tokens.push((trimmed.trim_end(), position));
+            start += part.chars().count();
+        }
+
+        let ((key_token, key_position), modifier_tokens) = tokens.split_last().expect("split yields a token");
+        let mut modifiers = Modifiers::empty();
+
+        for (token, position) in modifier_tokens {
+            if token.is_empty() {
+                return Err(error(token, *position, "missing modifier before '+'".to_string()));
+            }
+            let Some(modifier) = find_modifier(token) else {
+                let reason = if find_key(token).is_some() {
+                    format!("'{}' is a key, only modifiers can come before the last '+'", token)
+                } else {
+                    format!("unknown modifier '{}'", token)
+                };
+                return Err(error(token, *position, reason));
+            };
+            if modifiers.contains(modifier) {
+                return Err(error(token, *position, format!("modifier '{}' appears twice", token)));
+            }
+            modifiers |= modifier;
+        }
+
+        if key_token.is_empty() {
+            return Err(error(key_token, *key_position, "missing key".to_string()));
+        }
+        if find_modifier(key_token).is_some() {
+            return Err(error(
+                key_token,
+                *key_position,
+                format!("'{}' is a modifier, the shortcut needs a key after it", key_token),
+            ));
+        }
+        let key = find_key(key_token)
+            .ok_or_else(|| error(key_token, *key_position, format!("unknown key '{}'", key_token)))?;
+
+        Ok(Self { modifiers, key })
+    }
+
+    /// Returns the shortcut to register with the OS, which fires on the
+    /// physical key `key` whatever the keyboard layout
+    pub fn to_shortcut(self) -> Shortcut {
+        Shortcut::new(Some(self.modifiers), layout::registration_code(self.key))
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = KeyChordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in modifier_names(self.modifiers) {
+            write!(f, "{}+", name)?;
+        }
+        write!(f, "{}", key_name(self.key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(input: &str) -> String {
+        KeyChord::parse(input).unwrap().to_string()
+    }
+
+    // Returns the position and reason `input` is rejected with
+    fn rejected(input: &str) -> (usize, String) {
+        let error = KeyChord::parse(input).unwrap_err();
+        assert_eq!(error.input, input);
+        (error.position, error.reason)
+    }
+
+    #[test]
+    fn modifier_aliases() {
+        for control in ["Control", "Ctrl", "Ctl", "control", "CTRL", "⌃"] {
+            assert_eq!(canonical(&format!("{}+A", control)), "Control+A");
+        }
+        for alt in ["Alt", "Option", "Opt", "⌥"] {
+            assert_eq!(canonical(&format!("{}+A", alt)), "Alt+A");
+        }
+        for super_key in ["Super", "Win", "Windows", "Meta", "Cmd", "Command", "⌘"] {
+            assert_eq!(canonical(&format!("{}+A", super_key)), "Super+A");
+        }
+        assert_eq!(canonical("⇧+A"), "Shift+A");
+    }
+
+    #[test]
+    fn key_symbols_and_names() {
+        assert_eq!(canonical("Control+Alt+-"), "Control+Alt+Minus");
+        assert_eq!(canonical("Control+Alt+Dash"), "Control+Alt+Minus");
+        assert_eq!(canonical("Control+Alt+="), "Control+Alt+Equal");
+        assert_eq!(canonical("Control+["), "Control+BracketLeft");
+        assert_eq!(canonical("Control+\\"), "Control+Backslash");
+        assert_eq!(canonical("Control+`"), "Control+Backquote");
+        assert_eq!(canonical("⌃+⌥+←"), "Control+Alt+ArrowLeft");
+        assert_eq!(canonical("ctrl+alt+left"), "Control+Alt+ArrowLeft");
+        assert_eq!(canonical("Control+Esc"), "Control+Escape");
+        // W3C code names
+        assert_eq!(canonical("Control+KeyA"), "Control+A");
+        assert_eq!(canonical("Control+Digit1"), "Control+1");
+        assert_eq!(canonical("Control+Alt+Minus"), "Control+Alt+Minus");
+    }
+
+    #[test]
+    fn key_names_with_plus_or_minus_in_them() {
+        assert_eq!(canonical("Control+NumpadMinus"), "Control+NumpadSubtract");
+        assert_eq!(canonical("Control+NumpadPlus"), "Control+NumpadAdd");
+        assert_eq!(canonical("Control+Hyphen"), "Control+Minus");
+        // `+` only separates, the plus key is written by name
+        assert_eq!(rejected("Control++"), (9, "missing modifier before '+'".to_string()));
+    }
+
+    #[test]
+    fn modifiers_are_written_in_order() {
+        assert_eq!(canonical("Win+Shift+Alt+Ctrl+K"), "Control+Alt+Shift+Super+K");
+        assert_eq!(canonical("  Alt +  Control + K "), "Control+Alt+K");
+    }
+
+    #[test]
+    fn canonical_form_round_trips() {
+        let modifier_sets = [
+            Modifiers::empty(),
+            Modifiers::CONTROL,
+            Modifiers::CONTROL | Modifiers::ALT,
+            Modifiers::SHIFT | Modifiers::SUPER,
+            Modifiers::CONTROL | Modifiers::ALT | Modifiers::SHIFT | Modifiers::SUPER,
+        ];
+        for (key, name, aliases) in KEYS {
+            for modifiers in modifier_sets {
+                let chord = KeyChord { modifiers, key: *key };
+                let text = chord.to_string();
+                assert_eq!(KeyChord::parse(&text), Ok(chord), "{}", text);
+                assert!(text.ends_with(name));
+                for alias in *aliases {
+                    let prefix = &text[..text.len() - name.len()];
+                    assert_eq!(canonical(&format!("{}{}", prefix, alias)), text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_token() {
+        assert_eq!(rejected("Control+Alt+Foo"), (13, "unknown key 'Foo'".to_string()));
+        assert_eq!(rejected("Control+Hyper+A"), (9, "unknown modifier 'Hyper'".to_string()));
+        assert_eq!(
+            rejected("Control+A+B"),
+            (9, "'A' is a key, only modifiers can come before the last '+'".to_string())
+        );
+        // Positions count characters, not bytes
+        assert_eq!(rejected("⌃+⌥+Foo"), (5, "unknown key 'Foo'".to_string()));
+        // Whitespace around a token isn't part of it
+        assert_eq!(rejected("Control +  Foo"), (12, "unknown key 'Foo'".to_string()));
+        assert_eq!(KeyChord::parse("Control+Foo").unwrap_err().token, "Foo");
+    }
+
+    #[test]
+    fn empty_tokens() {
+        assert_eq!(rejected(""), (1, "missing key".to_string()));
+        assert_eq!(rejected("   "), (4, "missing key".to_string()));
+        assert_eq!(rejected("Control+Alt+"), (13, "missing key".to_string()));
+        assert_eq!(rejected("+A"), (1, "missing modifier before '+'".to_string()));
+        assert_eq!(rejected("Control++A"), (9, "missing modifier before '+'".to_string()));
+    }
+
+    #[test]
+    fn duplicate_modifier() {
+        assert_eq!(rejected("Control+Control+A"), (9, "modifier 'Control' appears twice".to_string()));
+        assert_eq!(rejected("Ctrl+Control+A"), (6, "modifier 'Control' appears twice".to_string()));
+    }
+
+    #[test]
+    fn modifier_without_a_key() {
+        assert_eq!(
+            rejected("Control+Alt"),
+            (9, "'Alt' is a modifier, the shortcut needs a key after it".to_string())
+        );
+        assert_eq!(
+            rejected("Win"),
+            (1, "'Win' is a modifier, the shortcut needs a key after it".to_string())
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::str::FromStr;
+use tauri::State;
+use tauri_plugin_global_shortcut::{Code, Modifiers};
+
+use crate::keychord::{self, KeyChord};
+use crate::layout;
+use crate::registry;
+use crate::shortcuts::{ShortcutManager, ShortcutsConfig};
+
+/// How shortcuts are written for people to read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortcutStyle {
+    /// Key symbols, e.g. `⌃⌥←`
+    Symbolic,
+    /// Abbreviated names, e.g. `Ctrl+Alt+Left`
+    #[default]
+    Short,
+    /// Full names, e.g. `Control + Alt + Left Arrow`
+    Long,
+}
+
+impl FromStr for ShortcutStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "symbolic" => Ok(Self::Symbolic),
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(format!("Unknown shortcut style: {} (expected symbolic, short or long)", s)),
+        }
+    }
+}
+
+// Modifiers in the order they're shown: symbol, short and long name
+const MODIFIER_LABELS: &[(Modifiers, &str, &str, &str)] = &[
+    (Modifiers::CONTROL, "⌃", "Ctrl", "Control"),
+    (Modifiers::ALT, "⌥", "Alt", "Alt"),
+    (Modifiers::SHIFT, "⇧", "Shift", "Shift"),
+    (Modifiers::SUPER, "⊞", "Win", "Windows"),
+];
+
+// Keys whose labels differ from their canonical name: symbol, short and long name
+const KEY_LABELS: &[(Code, &str, &str, &str)] = &[
+    (Code::ArrowLeft, "←", "Left", "Left Arrow"),
+    (Code::ArrowRight, "→", "Right", "Right Arrow"),
+    (Code::ArrowUp, "↑", "Up", "Up Arrow"),
+    (Code::ArrowDown, "↓", "Down", "Down Arrow"),
+    (Code::Enter, "↵", "Enter", "Enter"),
+    (Code::NumpadEnter, "⌤", "Num Enter", "Numpad Enter"),
+    (Code::Escape, "⎋", "Esc", "Escape"),
+    (Code::Tab, "⇥", "Tab", "Tab"),
+    (Code::Space, "␣", "Space", "Space"),
+    (Code::Backspace, "⌫", "Backspace", "Backspace"),
+    (Code::Delete, "⌦", "Del", "Delete"),
+    (Code::Insert, "Ins", "Ins", "Insert"),
+    (Code::Home, "⇱", "Home", "Home"),
+    (Code::End, "⇲", "End", "End"),
+    (Code::PageUp, "⇞", "PgUp", "Page Up"),
+    (Code::PageDown, "⇟", "PgDn", "Page Down"),
+    (Code::CapsLock, "⇪", "Caps", "Caps Lock"),
+    (Code::NumLock, "⇭", "NumLk", "Num Lock"),
+    (Code::ScrollLock, "ScrLk", "ScrLk", "Scroll Lock"),
+    (Code::PrintScreen, "PrtSc", "PrtSc", "Print Screen"),
+    (Code::Pause, "Pause", "Pause", "Pause"),
+    (Code::NumpadAdd, "Num+", "Num +", "Numpad Plus"),
+    (Code::NumpadSubtract, "Num-", "Num -", "Numpad Minus"),
+    (Code::NumpadMultiply, "Num*", "Num *", "Numpad Multiply"),
+    (Code::NumpadDivide, "Num/", "Num /", "Numpad Divide"),
+    (Code::NumpadDecimal, "Num.", "Num .", "Numpad Decimal"),
+    (Code::AudioVolumeMute, "🔇", "Mute", "Volume Mute"),
+    (Code::AudioVolumeDown, "🔉", "Vol-", "Volume Down"),
+    (Code::AudioVolumeUp, "🔊", "Vol+", "Volume Up"),
+    (Code::MediaPlayPause, "⏯", "Play", "Play/Pause"),
+    (Code::MediaStop, "⏹", "Stop", "Stop"),
+    (Code::MediaTrackNext, "⏭", "Next", "Next Track"),
+    (Code::MediaTrackPrevious, "⏮", "Prev", "Previous Track"),
+];
+
+// Returns the label of one key. Character keys are shown as printed on the
+// current keyboard layout in every style.
+fn key_label(key: Code, style: ShortcutStyle) -> String {
+    if let Some(label) = layout::key_label(key) {
+        return label;
+    }
+    if let Some((_, symbol, short, long)) = KEY_LABELS.iter().find(|(code, _, _, _)| *code == key) {
+        let label = match style {
+            ShortcutStyle::Symbolic => symbol,
+            ShortcutStyle::Short => short,
+            ShortcutStyle::Long => long,
+        };
+        return label.to_string();
+    }
+
+    let name = keychord::key_name(key);
+    match (name.strip_prefix("Numpad"), style) {
+        (Some(digit), ShortcutStyle::Symbolic) => format!("Num{}", digit),
+        (Some(digit), ShortcutStyle::Short) => format!("Num {}", digit),
+        (Some(digit), ShortcutStyle::Long) => format!("Numpad {}", digit),
+        (None, _) => name,
+    }
+}
+
+/// Renders one key combination in the given style
+pub fn format_chord(chord: &KeyChord, style: ShortcutStyle) -> String {
+    let mut parts: Vec<String> = MODIFIER_LABELS
+        .iter()
+        .filter(|(modifier, _, _, _)| chord.modifiers.contains(*modifier))
+        .map(|(_, symbol, short, long)| {
+            match style {
+                ShortcutStyle::Symbolic => symbol,
+                ShortcutStyle::Short => short,
+                ShortcutStyle::Long => long,
+            }
+            .to_string()
+        })
+        .collect();
+    parts.push(key_label(chord.key, style));
+
+    let separator = match style {
+        ShortcutStyle::Symbolic => "",
+        ShortcutStyle::Short => "+",
+        ShortcutStyle::Long => " + ",
+    };
+    parts.join(separator)
+}
+
+/// Renders a binding, chords included, in the given style. Steps that can't
+/// be parsed are shown as written.
+pub fn format_binding(binding: &str, style: ShortcutStyle) -> String {
+    let separator = match style {
+        ShortcutStyle::Long => ", then ",
+        _ => " ",
+    };
+    binding
+        .split_whitespace()
+        .map(|step| KeyChord::parse(step).map_or_else(|_| step.to_string(), |chord| format_chord(&chord, style)))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Lists every action with its shortcuts, the window mode keys and the
+/// profile shortcuts as a Markdown cheat sheet
+pub fn cheat_sheet(config: &ShortcutsConfig, style: ShortcutStyle) -> String {
+    let format_all = |bindings: &[String]| {
+        bindings
+            .iter()
+            .map(|binding| format!("`{}`", format_binding(binding, style)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut sheet = String::from("# Galaxy Shortcuts\n\n| Action | Shortcuts |\n| --- | --- |\n");
+    for descriptor in registry::ACTIONS {
+        let bindings = config
+            .get_all_shortcuts()
+            .get(descriptor.id)
+            .map(|cfg| cfg.bindings.clone())
+            .unwrap_or_default();
+        let _ = writeln!(sheet, "| {} | {} |", descriptor.name, format_all(&bindings));
+    }
+
+    if config.window_mode.activation_shortcut().is_some() {
+        let _ = write!(
+            sheet,
+            "\n## Window Mode\n\nPress `{}`, then:\n\n| Key | Action |\n| --- | --- |\n",
+            format_binding(&config.window_mode.shortcut, style)
+        );
+        for (key, action_id) in &config.window_mode.keys {
+            let name = registry::find(action_id).map_or(action_id.as_str(), |descriptor| descriptor.name);
+            let _ = writeln!(sheet, "| `{}` | {} |", format_binding(key, style), name);
+        }
+    }
+
+    if !config.profile_shortcuts.is_empty() {
+        sheet.push_str("\n## Profiles\n\n| Profile | Shortcut |\n| --- | --- |\n");
+        for (profile, shortcut) in &config.profile_shortcuts {
+            let _ = writeln!(sheet, "| {} | `{}` |", profile, format_binding(shortcut, style));
+        }
+    }
+
+    sheet
+}
+
+/// Renders a shortcut for display, in the short style unless `style` is given
+#[tauri::command]
+pub fn format_shortcut(shortcut: String, style: Option<ShortcutStyle>) -> Result<String, String> {
+    for step in shortcut.split_whitespace() {
+        KeyChord::parse(step).map_err(|e| e.to_string())?;
+    }
+    Ok(format_binding(&shortcut, style.unwrap_or_default()))
+}
+
+/// Returns the cheat sheet of the current settings as Markdown
+#[tauri::command]
+pub async fn get_cheat_sheet(style: Option<ShortcutStyle>, state: State<'_, ShortcutManager>) -> Result<String, String> {
+    let config = state.shortcuts.lock().map_err(|e| e.to_string())?;
+    Ok(cheat_sheet(&config, style.unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    // Character keys are shown as the current keyboard layout prints them, so
+    // only keys labelled the same everywhere are used here
+    fn all_styles(binding: &str) -> [String; 3] {
+        [ShortcutStyle::Symbolic, ShortcutStyle::Short, ShortcutStyle::Long].map(|style| format_binding(binding, style))
+    }
+
+    #[test]
+    fn styles_are_parsed_ignoring_case() {
+        assert_eq!("symbolic".parse(), Ok(ShortcutStyle::Symbolic));
+        assert_eq!("Short".parse(), Ok(ShortcutStyle::Short));
+        assert_eq!("LONG".parse(), Ok(ShortcutStyle::Long));
+        let error = "fancy".parse::<ShortcutStyle>().unwrap_err();
+        assert!(error.contains("expected symbolic, short or long"), "{error}");
+        assert_eq!(ShortcutStyle::default(), ShortcutStyle::Short);
+    }
+
+    #[test]
+    fn modifiers_and_arrows_in_every_style() {
+        assert_eq!(all_styles("Control+Alt+ArrowLeft"), ["⌃⌥←", "Ctrl+Alt+Left", "Control + Alt + Left Arrow"]);
+        assert_eq!(
+            all_styles("Shift+Super+PageDown"),
+            ["⇧⊞⇟", "Shift+Win+PgDn", "Shift + Windows + Page Down"]
+        );
+        assert_eq!(all_styles("Alt+F4"), ["⌥F4", "Alt+F4", "Alt + F4"]);
+    }
+
+    #[test]
+    fn modifiers_are_shown_in_a_fixed_order() {
+        let chord = KeyChord::parse("Super+Shift+Alt+Control+ArrowUp").unwrap();
+        assert_eq!(format_chord(&chord, ShortcutStyle::Short), "Ctrl+Alt+Shift+Win+Up");
+    }
+
+    #[test]
+    fn numpad_keys() {
+        assert_eq!(all_styles("Control+Numpad5"), ["⌃Num5", "Ctrl+Num 5", "Control + Numpad 5"]);
+        assert_eq!(all_styles("NumpadAdd"), ["Num+", "Num +", "Numpad Plus"]);
+        assert_eq!(all_styles("NumpadEnter"), ["⌤", "Num Enter", "Numpad Enter"]);
+    }
+
+    #[test]
+    fn chords_show_each_step() {
+        assert_eq!(
+            all_styles("Control+Alt+Home ArrowRight"),
+            ["⌃⌥⇱ →", "Ctrl+Alt+Home Right", "Control + Alt + Home, then Right Arrow"]
+        );
+    }
+
+    #[test]
+    fn unparseable_steps_are_shown_as_written() {
+        assert_eq!(format_binding("Control+Alt+Hyper", ShortcutStyle::Short), "Control+Alt+Hyper");
+        assert_eq!(
+            format_binding("Control+Alt+ArrowUp Hyper+Nope", ShortcutStyle::Long),
+            "Control + Alt + Up Arrow, then Hyper+Nope"
+        );
+    }
+
+    #[test]
+    fn cheat_sheet_lists_actions_window_mode_and_profiles() {
+        let mut config = ShortcutsConfig::default();
+        config.shortcuts.get_mut("leftHalf").unwrap().bindings =
+            vec!["Control+Alt+ArrowLeft".to_string(), "Control+Alt+Home ArrowLeft".to_string()];
+        config.shortcuts.get_mut("center").unwrap().bindings.clear();
+        config.window_mode.keys = BTreeMap::from([("Numpad1".to_string(), "bottomLeft".to_string())]);
+        config.profile_shortcuts = BTreeMap::from([("work".to_string(), "Control+Alt+F9".to_string())]);
+
+        let sheet = cheat_sheet(&config, ShortcutStyle::Symbolic);
+        assert!(sheet.starts_with("# Galaxy Shortcuts\n\n| Action | Shortcuts |\n| --- | --- |\n"), "{sheet}");
+        assert!(sheet.contains("| Snap to Left Half | `⌃⌥←`, `⌃⌥⇱ ←` |\n"), "{sheet}");
+        assert!(sheet.contains("| Center Window |  |\n"), "{sheet}");
+        assert!(sheet.contains("\n## Window Mode\n\nPress `⌃⌥␣`, then:\n"), "{sheet}");
+        assert!(sheet.contains("| `Num1` | Snap to Bottom Left |\n"), "{sheet}");
+        assert!(sheet.ends_with("\n## Profiles\n\n| Profile | Shortcut |\n| --- | --- |\n| work | `⌃⌥F9` |\n"), "{sheet}");
+    }
+
+    #[test]
+    fn cheat_sheet_leaves_out_what_is_disabled() {
+        let mut config = ShortcutsConfig::default();
+        config.window_mode.shortcut.clear();
+        config.profile_shortcuts.clear();
+
+        let sheet = cheat_sheet(&config, ShortcutStyle::Short);
+        assert!(!sheet.contains("## Window Mode"), "{sheet}");
+        assert!(!sheet.contains("## Profiles"), "{sheet}");
+    }
+}
@@ -0,0 +1,184 @@
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt};
+use winapi::shared::minwindef::HKL;
+use winapi::um::winuser::{
+    GetForegroundWindow, GetKeyboardLayout, GetWindowThreadProcessId, MapVirtualKeyExW, MAPVK_VK_TO_CHAR,
+    MAPVK_VSC_TO_VK,
+};
+
+use crate::chords;
+use crate::conflicts;
+use crate::shortcuts::{self, ShortcutsConfig};
+use crate::window_mode;
+
+// How often the focused window's keyboard layout is checked. Windows reports
+// an input language change only to the thread whose language changed, so
+// another process has to poll for it.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// Keyboard layout the shortcuts are registered for, 0 until first needed
+static ACTIVE_LAYOUT: AtomicUsize = AtomicUsize::new(0);
+
+// Keys whose meaning depends on the keyboard layout, with their scan code
+// (set 1) and the virtual key the shortcut plugin registers them under,
+// which is the one they produce on a US layout
+const LAYOUT_KEYS: &[(Code, u32, u32)] = &[
+    (Code::Backquote, 0x29, 0xC0),
+    (Code::Digit1, 0x02, 0x31),
+    (Code::Digit2, 0x03, 0x32),
+    (Code::Digit3, 0x04, 0x33),
+    (Code::Digit4, 0x05, 0x34),
+    (Code::Digit5, 0x06, 0x35),
+    (Code::Digit6, 0x07, 0x36),
+    (Code::Digit7, 0x08, 0x37),
+    (Code::Digit8, 0x09, 0x38),
+    (Code::Digit9, 0x0A, 0x39),
+    (Code::Digit0, 0x0B, 0x30),
+    (Code::Minus, 0x0C, 0xBD),
+    (Code::Equal, 0x0D, 0xBB),
+    (Code::KeyQ, 0x10, 0x51),
+    (Code::KeyW, 0x11, 0x57),
+    (Code::KeyE, 0x12, 0x45),
+    (Code::KeyR, 0x13, 0x52),
+    (Code::KeyT, 0x14, 0x54),
+    (Code::KeyY, 0x15, 0x59),
+    (Code::KeyU, 0x16, 0x55),
+    (Code::KeyI, 0x17, 0x49),
+    (Code::KeyO, 0x18, 0x4F),
+    (Code::KeyP, 0x19, 0x50),
+    (Code::BracketLeft, 0x1A, 0xDB),
+    (Code::BracketRight, 0x1B, 0xDD),
+    (Code::KeyA, 0x1E, 0x41),
+    (Code::KeyS, 0x1F, 0x53),
+    (Code::KeyD, 0x20, 0x44),
+    (Code::KeyF, 0x21, 0x46),
+    (Code::KeyG, 0x22, 0x47),
+    (Code::KeyH, 0x23, 0x48),
+    (Code::KeyJ, 0x24, 0x4A),
+    (Code::KeyK, 0x25, 0x4B),
+    (Code::KeyL, 0x26, 0x4C),
+    (Code::Semicolon, 0x27, 0xBA),
+    (Code::Quote, 0x28, 0xDE),
+    (Code::Backslash, 0x2B, 0xDC),
+    (Code::KeyZ, 0x2C, 0x5A),
+    (Code::KeyX, 0x2D, 0x58),
+    (Code::KeyC, 0x2E, 0x43),
+    (Code::KeyV, 0x2F, 0x56),
+    (Code::KeyB, 0x30, 0x42),
+    (Code::KeyN, 0x31, 0x4E),
+    (Code::KeyM, 0x32, 0x4D),
+    (Code::Comma, 0x33, 0xBC),
+    (Code::Period, 0x34, 0xBE),
+    (Code::Slash, 0x35, 0xBF),
+];
+
+// Returns the scan code of a layout-dependent key
+fn scan_code(code: Code) -> Option<u32> {
+    LAYOUT_KEYS
+        .iter()
+        .find(|(key, _, _)| *key == code)
+        .map(|(_, scan_code, _)| *scan_code)
+}
+
+// Returns the keyboard layout of the focused window. Layouts are per thread,
+// so Galaxy's own thread layout says nothing about what the user types in.
+fn foreground_layout() -> HKL {
+    unsafe { GetKeyboardLayout(GetWindowThreadProcessId(GetForegroundWindow(), null_mut())) }
+}
+
+// Returns the layout the shortcuts are registered for, picking the focused
+// window's on first use
+fn active_layout() -> HKL {
+    let layout = ACTIVE_LAYOUT.load(Ordering::Relaxed);
+    if layout != 0 {
+        return layout as HKL;
+    }
+    let current = foreground_layout() as usize;
+    match ACTIVE_LAYOUT.compare_exchange(0, current, Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => current as HKL,
+        Err(chosen) => chosen as HKL,
+    }
+}
+
+/// Returns the key to register so that the physical key `code` triggers the
+/// shortcut under the active keyboard layout.
+///
+/// The shortcut plugin registers keys by the character they produce on a US
+/// layout, so on a German layout the key right of `P` (`Ü`) has to be
+/// registered as `Semicolon`, the US key sharing its virtual key.
+pub fn registration_code(code: Code) -> Code {
+    let Some(scan_code) = scan_code(code) else {
+        return code;
+    };
+    let virtual_key = unsafe { MapVirtualKeyExW(scan_code, MAPVK_VSC_TO_VK, active_layout()) };
+    LAYOUT_KEYS
+        .iter()
+        .find(|(_, _, us_virtual_key)| *us_virtual_key == virtual_key)
+        .map(|(key, _, _)| *key)
+        .unwrap_or(code)
+}
+
+/// Returns what the physical key `code` is labelled on the active keyboard
+/// layout, e.g. `Ü` for `BracketLeft` on a German layout. `None` for keys
+/// that are labelled the same everywhere.
+pub fn key_label(code: Code) -> Option<String> {
+    let scan_code = scan_code(code)?;
+    let layout = active_layout();
+    let character = unsafe {
+        let virtual_key = MapVirtualKeyExW(scan_code, MAPVK_VSC_TO_VK, layout);
+        // Dead keys, such as `^` on a German layout, have the top bit set
+        MapVirtualKeyExW(virtual_key, MAPVK_VK_TO_CHAR, layout) & 0x7FFF_FFFF
+    };
+    char::from_u32(character)
+        .filter(|character| !character.is_control())
+        .map(|character| character.to_uppercase().collect())
+}
+
+// Switches the registrations to `layout` as a single transaction, restoring
+// the previous layout if any of them fails
+fn switch_layout(
+    app_handle: &AppHandle,
+    shortcuts_state: &Arc<Mutex<ShortcutsConfig>>,
+    layout: HKL,
+) -> Result<(), String> {
+    // Key layers grabbed keys under the previous layout, leave them first
+    window_mode::exit(app_handle);
+    chords::cancel(app_handle);
+
+    let _registrations = shortcuts::lock_registrations();
+    let config = shortcuts::snapshot(shortcuts_state)?;
+    let before = shortcuts::registrations(&config);
+    let previous = ACTIVE_LAYOUT.swap(layout as usize, Ordering::Relaxed);
+    let after = shortcuts::registrations(&config);
+
+    if let Err(e) = shortcuts::transition_registrations(app_handle.global_shortcut(), &before, &after) {
+        ACTIVE_LAYOUT.store(previous, Ordering::Relaxed);
+        return Err(e);
+    }
+    shortcuts::rebuild_dispatch_table(&config);
+    conflicts::emit_shortcut_status(app_handle, &config);
+    Ok(())
+}
+
+/// Watches the focused window's keyboard layout and registers the shortcuts
+/// again whenever it changes, so they stay on the same physical keys.
+pub fn watch_layout(app_handle: AppHandle, shortcuts_state: Arc<Mutex<ShortcutsConfig>>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let layout = foreground_layout();
+        if layout.is_null() || layout == active_layout() {
+            continue;
+        }
+
+        println!("Keyboard layout changed to {:?}, registering shortcuts again", layout);
+        match switch_layout(&app_handle, &shortcuts_state, layout) {
+            Ok(()) => crate::refresh_tray_menu(&app_handle),
+            Err(e) => eprintln!("Failed to register shortcuts for the new keyboard layout: {}", e),
+        }
+    });
+}
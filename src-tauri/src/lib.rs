@@ -1,26 +1,53 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
 
+mod autorepeat;
+mod bundle;
+mod chords;
+mod config;
+mod control;
+mod conflicts;
+mod importers;
+mod ipc;
+mod keychord;
+mod labels;
+mod layout;
+mod pause;
+mod profiles;
+mod registry;
 mod shortcuts;
+mod suspend;
+mod watcher;
 mod window_actions;
+mod window_layouts;
+mod window_mode;
 
-use shortcuts::{update_shortcut, ShortcutManager};
+use shortcuts::{add_binding, remove_binding, update_shortcut, ShortcutManager};
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::TrayIconBuilder,
     Manager, AppHandle,
 };
+use winapi::um::shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
 use winapi::um::winuser::{EnumDisplayMonitors, GetMonitorInfoW, MONITORINFO, MONITORINFOEXW};
 use window_actions::*;
 
+pub use ipc::Request;
+
 // Global storage for monitor information
 static MONITORS: Lazy<Mutex<Vec<MonitorInfo>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
-// Global debug mode flag
-static DEBUG_MODE: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
+// Id and default tooltip of the system tray icon
+const TRAY_ID: &str = "main_tray";
+const TRAY_TOOLTIP: &str = "Galaxy Window Manager";
+
+// Global debug mode flag, read on every keypress so it's atomic rather than locked
+static DEBUG_MODE: AtomicBool = AtomicBool::new(false);
 
 #[derive(Clone, Debug)]
 pub struct MonitorInfo {
@@ -34,6 +61,16 @@ pub struct MonitorInfo {
     pub work_top: i32,
     pub work_right: i32,
     pub work_bottom: i32,
+    // Effective DPI of the monitor (96 at 100% scaling)
+    pub dpi: u32,
+    pub scale_factor: f32,
+}
+
+impl MonitorInfo {
+    /// Converts a logical (100% scaling) pixel value into physical pixels on this monitor.
+    pub fn to_physical(&self, logical: i32) -> i32 {
+        (logical as f32 * self.scale_factor).round() as i32
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -54,9 +91,11 @@ pub struct ActionContext {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
-enum Action {
+pub enum Action {
     MoveLeft,
     MoveRight,
+    /// Moves the window to the monitor with this number, counting from 1 on the left
+    MoveToMonitor { number: usize },
     Maximize { gutter: i32 },
     LeftHalf,
     RightHalf,
@@ -79,136 +118,131 @@ enum Action {
 }
 
 impl Action {
-    fn get_handler(&self) -> Box<dyn WindowActionHandler> {
-        match self {
-            Action::MoveLeft => Box::new(MoveLeftAction),
-            Action::MoveRight => Box::new(MoveRightAction),
-            Action::Maximize { gutter } => Box::new(MaximizeAction { gutter: *gutter }),
-            Action::LeftHalf => Box::new(LeftHalfAction),
-            Action::RightHalf => Box::new(RightHalfAction),
-            Action::TopHalf => Box::new(TopHalfAction),
-            Action::BottomHalf => Box::new(BottomHalfAction),
-            Action::TopLeft => Box::new(TopLeftAction),
-            Action::TopRight => Box::new(TopRightAction),
-            Action::BottomLeft => Box::new(BottomLeftAction),
-            Action::BottomRight => Box::new(BottomRightAction),
-            Action::FirstThird => Box::new(FirstThirdAction),
-            Action::CenterThird => Box::new(CenterThirdAction),
-            Action::LastThird => Box::new(LastThirdAction),
-            Action::FirstTwoThirds => Box::new(FirstTwoThirdsAction),
-            Action::LastTwoThirds => Box::new(LastTwoThirdsAction),
-            Action::Center => Box::new(CenterAction),
-            Action::MakeLarger => Box::new(MakeLargerAction),
-            Action::MakeSmaller => Box::new(MakeSmallerAction),
-            Action::MaximizeHeight => Box::new(MaximizeHeightAction),
-            _ => Box::new(NoOpAction),
-        }
+    /// Returns true for actions that make sense to repeat while their shortcut is held
+    pub fn is_repeatable(&self) -> bool {
+        matches!(self, Action::MakeLarger | Action::MakeSmaller)
+    }
+
+    /// Creates the handler that carries out this action, see [`registry::handler`]
+    pub fn get_handler(&self) -> Box<dyn WindowActionHandler> {
+        registry::handler(self)
     }
 }
 
 /// Moves the currently focused window to a new position and/or size.
 #[tauri::command]
 fn move_window(action: Option<Action>) -> Result<(), String> {
-    let debug_enabled = *DEBUG_MODE.lock().unwrap();
+    let debug_enabled = DEBUG_MODE.load(Ordering::Relaxed);
     if debug_enabled {
         println!("move_window called with action: {:?}", action);
     }
 
     #[cfg(target_os = "windows")]
     {
-        use std::ptr;
-        use winapi::shared::windef::HWND;
-        use winapi::um::winuser::SWP_FRAMECHANGED;
-        use winapi::um::winuser::{GetForegroundWindow, GetWindowRect, SetWindowPos};
-
-        unsafe {
-            let hwnd: HWND = GetForegroundWindow();
-            if hwnd.is_null() {
-                return Err("No focused window found".to_string());
-            }
+        use winapi::um::winuser::GetForegroundWindow;
 
-            // Get current window position and size
-            let mut rect = std::mem::zeroed();
-            if GetWindowRect(hwnd, &mut rect) == 0 {
-                return Err("Failed to get window position".to_string());
-            }
+        let hwnd = unsafe { GetForegroundWindow() };
+        if hwnd.is_null() {
+            return Err("No focused window found".to_string());
+        }
+        place_window(hwnd, action.as_ref().unwrap_or(&Action::None), None)
+    }
 
-            // Update monitor information
-            get_monitor_info();
-            let monitors = MONITORS.lock().unwrap().clone();
-
-            let window_center_x = (rect.left + rect.right) / 2;
-            let window_center_y = (rect.top + rect.bottom) / 2;
-
-            // Find current monitor
-            let current_monitor = monitors
-                .iter()
-                .position(|m| {
-                    window_center_x >= m.left
-                        && window_center_x < m.right
-                        && window_center_y >= m.top
-                        && window_center_y < m.bottom
-                })
-                .unwrap_or(0);
-
-            let window_info = WindowInfo {
-                current_width: rect.right - rect.left,
-                current_height: rect.bottom - rect.top,
-                center_x: window_center_x,
-                center_y: window_center_y,
-            };
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("Function not implemented for this OS".to_string())
+    }
+}
 
-            // Get the action handler
-            let action_ref = action.as_ref().unwrap_or(&Action::None);
-            let handler = action_ref.get_handler();
+/// Runs `action` on the window `hwnd`. `monitor` is the index in [`MONITORS`]
+/// to place it on instead of the one the action picks.
+#[cfg(target_os = "windows")]
+fn place_window(hwnd: winapi::shared::windef::HWND, action: &Action, monitor: Option<usize>) -> Result<(), String> {
+    use std::ptr;
+    use winapi::um::winuser::SWP_FRAMECHANGED;
+    use winapi::um::winuser::{GetWindowRect, SetWindowPos};
 
-            // Determine target monitor
-            let target_monitor = handler.get_target_monitor(current_monitor, &monitors);
+    let debug_enabled = DEBUG_MODE.load(Ordering::Relaxed);
 
-            // Create action context
-            let context = ActionContext {
-                current_monitor,
-                target_monitor,
-                monitors,
-                window_info,
-            };
+    unsafe {
+        // Get current window position and size
+        let mut rect = std::mem::zeroed();
+        if GetWindowRect(hwnd, &mut rect) == 0 {
+            return Err("Failed to get window position".to_string());
+        }
 
-            // Calculate new position and size
-            let (new_width, new_height, new_x, new_y) =
-                handler.calculate_position(&context, hwnd)?;
-
-            // Apply the changes
-            let result = SetWindowPos(
-                hwnd,
-                ptr::null_mut(),
-                new_x,
-                new_y,
-                new_width,
-                new_height,
-                SWP_FRAMECHANGED,
-            );
+        // Update monitor information
+        get_monitor_info();
+        let monitors = MONITORS.lock().unwrap().clone();
+
+        let window_center_x = (rect.left + rect.right) / 2;
+        let window_center_y = (rect.top + rect.bottom) / 2;
+
+        // Find current monitor
+        let current_monitor = window_actions::geometry::current_monitor(
+            rect.left,
+            rect.top,
+            rect.right,
+            rect.bottom,
+            &monitors,
+        )
+        .ok_or_else(|| "No monitors found".to_string())?;
+
+        let window_info = WindowInfo {
+            current_width: rect.right - rect.left,
+            current_height: rect.bottom - rect.top,
+            center_x: window_center_x,
+            center_y: window_center_y,
+        };
+
+        // Get the action handler
+        let handler = action.get_handler();
+
+        // Determine target monitor
+        let target_monitor = match monitor {
+            Some(monitor) if monitor < monitors.len() => monitor,
+            Some(monitor) => return Err(format!("No monitor at index {}", monitor)),
+            None => handler.get_target_monitor(current_monitor, &monitors),
+        };
+
+        // Create action context
+        let context = ActionContext {
+            current_monitor,
+            target_monitor,
+            monitors,
+            window_info,
+        };
+
+        // Calculate new position and size
+        let (new_width, new_height, new_x, new_y) =
+            handler.calculate_position(&context, hwnd)?;
+
+        // Apply the changes
+        let result = SetWindowPos(
+            hwnd,
+            ptr::null_mut(),
+            new_x,
+            new_y,
+            new_width,
+            new_height,
+            SWP_FRAMECHANGED,
+        );
 
-            if result != 0 {
-                if debug_enabled {
-                    println!(
-                        "Window moved successfully to {}x{} at ({},{})",
-                        new_width, new_height, new_x, new_y
-                    );
-                }
-                Ok(())
-            } else {
-                if debug_enabled {
-                    println!("Failed to move/resize window");
-                }
-                Err("Failed to move/resize window".to_string())
+        if result != 0 {
+            if debug_enabled {
+                println!(
+                    "Window moved successfully to {}x{} at ({},{})",
+                    new_width, new_height, new_x, new_y
+                );
+            }
+            Ok(())
+        } else {
+            if debug_enabled {
+                println!("Failed to move/resize window");
             }
+            Err("Failed to move/resize window".to_string())
         }
     }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        Err("Function not implemented for this OS".to_string())
-    }
 }
 
 #[tauri::command]
@@ -248,11 +282,21 @@ unsafe extern "system" fn enum_monitor_callback(
     {
         let width = monitor_info.rcMonitor.right - monitor_info.rcMonitor.left;
         let height = monitor_info.rcMonitor.bottom - monitor_info.rcMonitor.top;
-        let debug_enabled = *DEBUG_MODE.lock().unwrap();
+
+        // Fall back to 100% scaling if the effective DPI can't be queried
+        let mut dpi_x: u32 = 0;
+        let mut dpi_y: u32 = 0;
+        let dpi = if GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) == 0 && dpi_x != 0 {
+            dpi_x
+        } else {
+            96
+        };
+
+        let debug_enabled = DEBUG_MODE.load(Ordering::Relaxed);
         if debug_enabled {
             println!(
-                "Monitor at ({}, {}): {}x{}",
-                monitor_info.rcMonitor.left, monitor_info.rcMonitor.top, width, height
+                "Monitor at ({}, {}): {}x{} @ {} DPI",
+                monitor_info.rcMonitor.left, monitor_info.rcMonitor.top, width, height, dpi
             );
         }
 
@@ -266,6 +310,8 @@ unsafe extern "system" fn enum_monitor_callback(
             work_top: monitor_info.rcWork.top,
             work_right: monitor_info.rcWork.right,
             work_bottom: monitor_info.rcWork.bottom,
+            dpi,
+            scale_factor: dpi as f32 / 96.0, // 96 is the default DPI
         });
     }
 
@@ -291,22 +337,273 @@ fn toggle_window(app: AppHandle) {
     }
 }
 
-pub fn run(debug_mode: bool) {
+/// Updates the tray tooltip to show whether a key layer (a pending chord or
+/// window mode) is currently grabbing keys
+fn refresh_tray_tooltip(app_handle: &AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let profile = profiles::active();
+    let title = if profile == profiles::DEFAULT_PROFILE {
+        TRAY_TOOLTIP.to_string()
+    } else {
+        format!("{} ({})", TRAY_TOOLTIP, profile)
+    };
+
+    let tooltip = if pause::is_paused() {
+        match pause::remaining() {
+            Some(remaining) => format!("{} - paused for {} more minutes", title, remaining.as_secs().div_ceil(60)),
+            None => format!("{} - paused", title),
+        }
+    } else if let Some(reason) = suspend::reason() {
+        format!("{} - suspended while {} is focused", title, reason)
+    } else if window_mode::is_active() {
+        format!("{} - window mode (Esc to exit)", title)
+    } else if chords::is_pending() {
+        format!("{} - waiting for chord key...", title)
+    } else {
+        title
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+}
+
+// The tray icon, and its faded version shown while shortcuts are paused
+const TRAY_ICON: &[u8] = include_bytes!("../icons/32x32.png");
+
+/// Fades the tray icon while shortcuts are paused
+fn refresh_tray_icon(app_handle: &AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let Ok(icon) = tauri::image::Image::from_bytes(TRAY_ICON) else {
+        return;
+    };
+
+    if !pause::is_paused() {
+        let _ = tray.set_icon(Some(icon));
+        return;
+    }
+    // Grey and half transparent
+    let mut rgba = icon.rgba().to_vec();
+    for pixel in rgba.chunks_exact_mut(4) {
+        let grey = ((pixel[0] as u32 * 30 + pixel[1] as u32 * 59 + pixel[2] as u32 * 11) / 100) as u8;
+        pixel[..3].fill(grey);
+        pixel[3] /= 2;
+    }
+    let faded = tauri::image::Image::new_owned(rgba, icon.width(), icon.height());
+    let _ = tray.set_icon(Some(faded));
+}
+
+// Tray menu item ids that pause shortcuts, with the pause length in minutes (0 until resumed)
+const PAUSE_MENU_ITEMS: &[(&str, &str, u64)] = &[
+    ("pause:0", "Until Resumed", 0),
+    ("pause:15", "For 15 Minutes", 15),
+    ("pause:60", "For 1 Hour", 60),
+];
+
+// Prefix of the tray menu item ids that switch profile
+const PROFILE_MENU_PREFIX: &str = "profile:";
+
+// Reads a setting for the tray menu, which may be built before the settings are managed
+fn read_config<T>(app_handle: &AppHandle, read: impl FnOnce(&shortcuts::ShortcutsConfig) -> T) -> Option<T> {
+    let state = app_handle.try_state::<ShortcutManager>()?;
+    let config = state.shortcuts.lock().ok()?;
+    Some(read(&config))
+}
+
+/// Builds the tray menu, with a submenu listing the profiles
+fn build_tray_menu(app_handle: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let open_settings = MenuItem::with_id(app_handle, "open_settings", "Open Settings", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app_handle, "quit", "Quit", true, None::<&str>)?;
+
+    // Profiles with a shortcut show it next to their name
+    let profile_shortcuts = read_config(app_handle, |config| config.profile_shortcuts.clone()).unwrap_or_default();
+    let active = profiles::active();
+    let profile_items = profiles::names()
+        .into_iter()
+        .map(|name| {
+            let id = format!("{}{}", PROFILE_MENU_PREFIX, name);
+            let text = match profile_shortcuts.get(&name) {
+                Some(shortcut) => format!(
+                    "{}\t{}",
+                    name,
+                    labels::format_binding(shortcut, labels::ShortcutStyle::Short)
+                ),
+                None => name.clone(),
+            };
+            CheckMenuItem::with_id(app_handle, id, text, true, name == active, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let profile_refs: Vec<&dyn IsMenuItem<tauri::Wry>> =
+        profile_items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>).collect();
+    let profile_menu = Submenu::with_items(app_handle, "Profile", true, &profile_refs)?;
+
+    // Pausing offers a choice of durations, resuming is a single item
+    let pause_item: Box<dyn IsMenuItem<tauri::Wry>> = if pause::is_paused() {
+        let text = match read_config(app_handle, |config| config.pause_shortcut.clone()) {
+            Some(shortcut) if !shortcut.trim().is_empty() => format!(
+                "Resume Shortcuts\t{}",
+                labels::format_binding(&shortcut, labels::ShortcutStyle::Short)
+            ),
+            _ => "Resume Shortcuts".to_string(),
+        };
+        Box::new(MenuItem::with_id(app_handle, "resume", text, true, None::<&str>)?)
+    } else {
+        let items = PAUSE_MENU_ITEMS
+            .iter()
+            .map(|(id, text, _)| MenuItem::with_id(app_handle, *id, *text, true, None::<&str>))
+            .collect::<tauri::Result<Vec<_>>>()?;
+        let item_refs: Vec<&dyn IsMenuItem<tauri::Wry>> =
+            items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>).collect();
+        Box::new(Submenu::with_items(app_handle, "Pause Shortcuts", true, &item_refs)?)
+    };
+
+    let separator = PredefinedMenuItem::separator(app_handle)?;
+    Menu::with_items(
+        app_handle,
+        &[&open_settings, &profile_menu, pause_item.as_ref(), &separator, &quit],
+    )
+}
+
+/// Rebuilds the tray menu, e.g. after the profiles changed
+fn refresh_tray_menu(app_handle: &AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app_handle) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => eprintln!("Failed to rebuild tray menu: {}", e),
+    }
+}
+
+/// Returns the JSON Schema describing the settings file
+pub fn config_schema() -> String {
+    shortcuts::ShortcutsConfig::json_schema()
+}
+
+/// Imports another window manager's settings into the settings file, for the
+/// command line. Returns the import report as JSON.
+pub fn import_settings(source: &str, path: &Path, config_path: Option<PathBuf>) -> Result<String, String> {
+    config::set_config_path(config_path);
+    let source: importers::ImportSource = source.parse()?;
+    let report = importers::import_to_file(source, path)?;
+    serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+}
+
+/// Writes the settings, every profile included, to a portable bundle file
+pub fn export_config(path: &Path, config_path: Option<PathBuf>) -> Result<(), String> {
+    config::set_config_path(config_path);
+    let config = shortcuts::ShortcutsConfig::load().map_err(|e| e.to_string())?;
+    config::write_atomic(path, &bundle::export(&config)?)
+}
+
+/// Returns a Markdown cheat sheet of the shortcuts in the given style
+/// (`symbolic`, `short` or `long`), for the command line
+pub fn cheat_sheet(style: &str, config_path: Option<PathBuf>) -> Result<String, String> {
+    config::set_config_path(config_path);
+    let style: labels::ShortcutStyle = style.parse()?;
+    let config = shortcuts::ShortcutsConfig::load().map_err(|e| e.to_string())?;
+    Ok(labels::cheat_sheet(&config, style))
+}
+
+/// Imports a settings bundle into the settings file, merging it unless
+/// `replace` is set. Returns the changes as JSON; only previews them when
+/// `dry_run` is set.
+pub fn import_config(
+    path: &Path,
+    replace: bool,
+    dry_run: bool,
+    config_path: Option<PathBuf>,
+) -> Result<String, String> {
+    config::set_config_path(config_path);
+    let mode = if replace {
+        bundle::ImportMode::Replace
+    } else {
+        bundle::ImportMode::Merge
+    };
+    let preview = bundle::import_to_file(path, mode, dry_run)?;
+    serde_json::to_string_pretty(&preview).map_err(|e| e.to_string())
+}
+
+/// Hands `request` to the Galaxy instance already running, if any, and
+/// returns its answer
+pub fn send_to_running(request: &Request) -> Option<Result<String, String>> {
+    ipc::send(request)
+}
+
+/// Carries out a command line request in this process, for when no instance
+/// is running or the request names its own settings file
+pub fn run_one_shot(request: &Request, config_path: Option<PathBuf>) -> Result<String, String> {
+    config::set_config_path(config_path);
+    // Window and monitor coordinates are physical pixels, as in the app
+    unsafe {
+        winapi::um::shellscalingapi::SetProcessDpiAwareness(winapi::um::shellscalingapi::PROCESS_PER_MONITOR_DPI_AWARE);
+    }
+    control::execute(request)
+}
+
+/// Runs Galaxy. With `pause_minutes`, shortcuts start paused for that many
+/// minutes, or until resumed if it's 0.
+pub fn run(debug_mode: bool, config_path: Option<PathBuf>, profile: Option<String>, pause_minutes: Option<u64>) {
     // Set global debug mode
-    *DEBUG_MODE.lock().unwrap() = debug_mode;
+    DEBUG_MODE.store(debug_mode, Ordering::Relaxed);
+    let own_settings = config_path.is_some();
+    config::set_config_path(config_path);
     
     if debug_mode {
         println!("Galaxy Window Manager starting in debug mode...");
     }
+
+    // Another instance may have started since main() looked for one
+    let pipe = match ipc::claim() {
+        Ok(pipe) => pipe,
+        Err(e) => {
+            eprintln!("{}", e);
+            if debug_mode || own_settings {
+                eprintln!("--config and --debug only apply when Galaxy starts, quit the running instance to use them");
+            }
+            std::process::exit(1);
+        }
+    };
     // Create the shared shortcut config ONCE
-    let shortcuts_config = Arc::new(Mutex::new(
-        shortcuts::ShortcutsConfig::load().unwrap_or_default(),
-    ));
+    let mut initial_config = shortcuts::ShortcutsConfig::load().unwrap_or_default();
+    if let Some(name) = profile {
+        match profiles::activate(&name) {
+            Ok(config) => initial_config = config,
+            Err(e) => eprintln!("Failed to switch to profile '{}': {}", name, e),
+        }
+    }
+    let shortcuts_config = Arc::new(Mutex::new(initial_config));
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(ShortcutManager::new(shortcuts_config.clone()))
-        .invoke_handler(tauri::generate_handler![move_window, update_shortcut, toggle_window])
+        .invoke_handler(tauri::generate_handler![
+            move_window,
+            update_shortcut,
+            add_binding,
+            remove_binding,
+            shortcuts::update_action_trigger,
+            shortcuts::reset_shortcut,
+            shortcuts::reset_all_shortcuts,
+            profiles::list_profiles,
+            profiles::set_active_profile,
+            importers::import_settings,
+            bundle::export_config,
+            bundle::preview_import,
+            bundle::import_config,
+            toggle_window,
+            registry::list_actions,
+            labels::format_shortcut,
+            labels::get_cheat_sheet,
+            pause::pause_shortcuts,
+            pause::resume_shortcuts,
+            pause::get_pause_status,
+            conflicts::get_shortcut_status
+        ])
         .on_window_event(|window, event| {
             match event {
                 tauri::WindowEvent::CloseRequested { api, .. } => {
@@ -321,15 +618,13 @@ pub fn run(debug_mode: bool) {
             let shortcuts_config = shortcuts_config.clone();
             move |app| {
                 // Create tray menu
-                let open_settings = MenuItem::with_id(app, "open_settings", "Open Settings", true, None::<&str>)?;
-                let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-                let menu = Menu::with_items(app, &[&open_settings, &quit])?;
+                let menu = build_tray_menu(app.handle())?;
 
                 // Create system tray
-                let tray_icon = include_bytes!("../icons/32x32.png");
-                let image = tauri::image::Image::from_bytes(tray_icon)?;
-                let _tray = TrayIconBuilder::with_id("main_tray")
+                let image = tauri::image::Image::from_bytes(TRAY_ICON)?;
+                let _tray = TrayIconBuilder::with_id(TRAY_ID)
                     .icon(image)
+                    .tooltip(TRAY_TOOLTIP)
                     .menu(&menu)
                     .show_menu_on_left_click(false)
                     .on_menu_event(move |app, event| match event.id.as_ref() {
@@ -342,9 +637,22 @@ pub fn run(debug_mode: bool) {
                         "quit" => {
                             app.exit(0);
                         }
-                        _ => {}
+                        "resume" => {
+                            pause::toggle_async(app, None);
+                        }
+                        id => {
+                            if let Some(name) = id.strip_prefix(PROFILE_MENU_PREFIX) {
+                                profiles::switch_profile_async(app, name.to_string());
+                            } else if let Some((_, _, minutes)) =
+                                PAUSE_MENU_ITEMS.iter().find(|(item, _, _)| *item == id)
+                            {
+                                let duration = (*minutes > 0).then(|| std::time::Duration::from_secs(minutes * 60));
+                                pause::toggle_async(app, duration);
+                            }
+                        }
                     })
                     .build(app)?;
+                refresh_tray_tooltip(app.handle());
 
                 // Hide window on startup (start minimized to tray)
                 if let Some(window) = app.get_webview_window("main") {
@@ -360,6 +668,22 @@ pub fn run(debug_mode: bool) {
                     } else if debug_mode {
                         println!("Shortcuts registered successfully");
                     }
+
+                    if let Some(minutes) = pause_minutes {
+                        let duration = (minutes > 0).then(|| std::time::Duration::from_secs(minutes * 60));
+                        if let Err(e) = pause::pause(app.handle(), &shortcuts_config, duration) {
+                            eprintln!("Failed to pause shortcuts: {}", e);
+                        }
+                    }
+
+                    // Pick up hand edits of the settings file without a restart
+                    watcher::watch_config(app.handle().clone(), shortcuts_config.clone());
+                    // Keep shortcuts on the same physical keys when the keyboard layout changes
+                    layout::watch_layout(app.handle().clone(), shortcuts_config.clone());
+                    // Step aside while a fullscreen app or a listed process is focused
+                    suspend::watch_foreground(app.handle().clone(), shortcuts_config.clone());
+                    // Take requests from later launches
+                    ipc::serve(app.handle().clone(), pipe);
                 }
                 Ok(())
             }
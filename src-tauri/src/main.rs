@@ -1,7 +1,8 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "galaxy")]
@@ -10,6 +11,102 @@ struct Args {
     /// Enable debug mode with console output
     #[arg(long, help = "Enable debug logging and show console window")]
     debug: bool,
+
+    /// Path to the settings file
+    #[arg(long, value_name = "PATH", help = "Use a custom settings file instead of the one in the user config directory")]
+    config: Option<PathBuf>,
+
+    /// Profile to start with
+    #[arg(long, value_name = "NAME", help = "Switch to the named settings profile")]
+    profile: Option<String>,
+
+    /// Start with shortcuts paused
+    #[arg(long, value_name = "MINUTES", num_args = 0..=1, default_missing_value = "0", help = "Start with every shortcut but the pause shortcut paused, for MINUTES or until resumed")]
+    pause: Option<u64>,
+
+    /// Import shortcuts from a Rectangle export and exit
+    #[arg(long, value_name = "PATH", help = "Import shortcuts from Rectangle's exported RectangleConfig.json and exit")]
+    import_rectangle: Option<PathBuf>,
+
+    /// Import zones from a FancyZones layout file and exit
+    #[arg(long, value_name = "PATH", help = "Import zones from FancyZones' custom-layouts.json as window mode keys and exit")]
+    import_fancyzones: Option<PathBuf>,
+
+    /// Export the settings to a bundle and exit
+    #[arg(long, value_name = "PATH", help = "Export the settings, every profile included, to a bundle file and exit")]
+    export_config: Option<PathBuf>,
+
+    /// Import a settings bundle and exit
+    #[arg(long, value_name = "PATH", help = "Import a settings bundle, print the changes and exit")]
+    import_config: Option<PathBuf>,
+
+    /// Replace the settings instead of merging when importing a bundle
+    #[arg(long, requires = "import_config", help = "Replace the current settings with the bundle instead of merging it")]
+    replace: bool,
+
+    /// Only show what importing a bundle would change
+    #[arg(long, requires = "import_config", help = "Print what the import would change without changing anything")]
+    dry_run: bool,
+
+    /// Print a cheat sheet of the shortcuts and exit
+    #[arg(long, help = "Print a Markdown cheat sheet of the shortcuts and exit")]
+    cheat_sheet: bool,
+
+    /// How shortcuts are written in the cheat sheet
+    #[arg(long, value_name = "STYLE", default_value = "short", requires = "cheat_sheet", help = "Write shortcuts as symbolic (⌃⌥←), short (Ctrl+Alt+Left) or long (Control + Alt + Left Arrow)")]
+    style: String,
+
+    /// Write the settings file JSON Schema and exit
+    #[arg(long, value_name = "PATH", help = "Write the JSON Schema of the settings file to PATH and exit")]
+    export_schema: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Commands for scripts, run by the running instance or, without one, by this process
+#[derive(Subcommand)]
+enum Command {
+    /// Run a window action on the focused window, e.g. `left-half` or `move-to-monitor 2`
+    Action {
+        /// Action name, in kebab-case or as its settings id
+        name: String,
+        /// Monitor number for `move-to-monitor`, counting from 1 on the left
+        monitor: Option<usize>,
+    },
+    /// Apply or otherwise manage window layouts
+    Layout {
+        #[command(subcommand)]
+        command: LayoutCommand,
+    },
+    /// List the monitors as JSON
+    Monitors,
+    /// List the application windows as JSON
+    Windows,
+}
+
+#[derive(Subcommand)]
+enum LayoutCommand {
+    /// Move every open window of the layout into place, e.g. `apply work`
+    Apply {
+        /// Layout name, as set up under `layouts` in the settings file
+        name: String,
+    },
+}
+
+#[cfg(target_os = "windows")]
+fn attach_console() {
+    use winapi::um::wincon::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // Release builds have no console of their own, print to the caller's
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_console() {
+    // No-op on non-Windows platforms
 }
 
 #[cfg(target_os = "windows")]
@@ -33,11 +130,111 @@ fn allocate_console() {
 
 fn main() {
     let args = Args::parse();
+
+    if let Some(command) = args.command {
+        attach_console();
+        let request = match command {
+            Command::Action { name, monitor } => galaxy_lib::Request::Action { name, monitor },
+            Command::Layout {
+                command: LayoutCommand::Apply { name },
+            } => galaxy_lib::Request::Layout { name },
+            Command::Monitors => galaxy_lib::Request::Monitors,
+            Command::Windows => galaxy_lib::Request::Windows,
+        };
+        // The running instance reads its own settings file, so a request for
+        // another one is carried out here
+        let response = match args.config {
+            Some(path) => galaxy_lib::run_one_shot(&request, Some(path)),
+            None => galaxy_lib::send_to_running(&request).unwrap_or_else(|| galaxy_lib::run_one_shot(&request, None)),
+        };
+        match response {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(path) = args.export_schema {
+        if let Err(e) = std::fs::write(&path, galaxy_lib::config_schema()) {
+            eprintln!("Failed to write schema to {:?}: {}", path, e);
+            std::process::exit(1);
+        }
+        return;
+    }
     
+    if args.cheat_sheet {
+        match galaxy_lib::cheat_sheet(&args.style, args.config) {
+            Ok(sheet) => print!("{}", sheet),
+            Err(e) => {
+                eprintln!("Failed to build the cheat sheet: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(path) = args.export_config {
+        if let Err(e) = galaxy_lib::export_config(&path, args.config) {
+            eprintln!("Export failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(path) = args.import_config {
+        match galaxy_lib::import_config(&path, args.replace, args.dry_run, args.config) {
+            Ok(changes) => println!("{}", changes),
+            Err(e) => {
+                eprintln!("Import failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let import = [
+        ("rectangle", args.import_rectangle),
+        ("fancyzones", args.import_fancyzones),
+    ];
+    if let Some((source, path)) = import.into_iter().find_map(|(source, path)| path.map(|path| (source, path))) {
+        match galaxy_lib::import_settings(source, &path, args.config) {
+            Ok(report) => println!("{}", report),
+            Err(e) => {
+                eprintln!("Import failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // A second launch hands its options to the running instance instead.
+    // --config and --debug only apply to a new instance, so a launch with
+    // either isn't handed over and stops in run() if Galaxy is running.
+    if args.config.is_none() && !args.debug {
+        let request = galaxy_lib::Request::Launch {
+            profile: args.profile.clone(),
+            pause: args.pause,
+        };
+        match galaxy_lib::send_to_running(&request) {
+            Some(Ok(message)) => {
+                println!("{}", message);
+                return;
+            }
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            None => {}
+        }
+    }
+
     if args.debug {
         allocate_console();
         println!("Debug mode enabled - console output will be visible");
     }
     
-    galaxy_lib::run(args.debug)
+    galaxy_lib::run(args.debug, args.config, args.profile, args.pause)
 }
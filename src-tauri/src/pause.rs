@@ -0,0 +1,185 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+use crate::chords;
+use crate::shortcuts::{self, ShortcutManager, ShortcutsConfig};
+use crate::window_mode;
+
+struct Paused {
+    generation: u64,
+    // When a timed pause ends by itself
+    until: Option<Instant>,
+}
+
+// Set while every shortcut but the pause shortcut is unregistered
+static PAUSED: Lazy<Mutex<Option<Paused>>> = Lazy::new(|| Mutex::new(None));
+
+// Bumped on every pause so the timer of an earlier timed pause can tell it's stale
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Returns true while shortcuts are paused
+pub fn is_paused() -> bool {
+    PAUSED.lock().unwrap().is_some()
+}
+
+/// Returns how long a timed pause has left, `None` when not paused or
+/// paused until resumed
+pub fn remaining() -> Option<Duration> {
+    PAUSED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|paused| paused.until)
+        .map(|until| until.saturating_duration_since(Instant::now()))
+}
+
+/// Picks the shortcuts to register from the ones the config uses: only the
+/// pause shortcut while paused, all of them otherwise
+pub fn filter_registrations(config: &ShortcutsConfig, all: HashMap<u32, Shortcut>) -> HashMap<u32, Shortcut> {
+    if !is_paused() {
+        return all;
+    }
+    config
+        .pause_shortcut()
+        .map(|shortcut| HashMap::from([(shortcut.id(), shortcut)]))
+        .unwrap_or_default()
+}
+
+// Flips the pause state and brings the registrations in line, as a single
+// transaction: if a registration fails, the previous state is restored
+fn set_paused(
+    app_handle: &AppHandle,
+    shortcuts_state: &Arc<Mutex<ShortcutsConfig>>,
+    paused: Option<Paused>,
+) -> Result<(), String> {
+    let _registrations = shortcuts::lock_registrations();
+    let config = shortcuts::snapshot(shortcuts_state)?;
+    let before = shortcuts::registrations(&config);
+    let previous = std::mem::replace(&mut *PAUSED.lock().unwrap(), paused);
+    let after = shortcuts::registrations(&config);
+
+    if let Err(e) = shortcuts::transition_registrations(app_handle.global_shortcut(), &before, &after) {
+        *PAUSED.lock().unwrap() = previous;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Unregisters every shortcut except the pause shortcut, which resumes them.
+/// With a `duration`, the shortcuts come back by themselves once it's over;
+/// pausing again replaces the previous duration.
+///
+/// Must not be called from the shortcut handler, see [`toggle_async`].
+pub fn pause(
+    app_handle: &AppHandle,
+    shortcuts_state: &Arc<Mutex<ShortcutsConfig>>,
+    duration: Option<Duration>,
+) -> Result<(), String> {
+    // Key layers grab keys of their own, leave them first
+    window_mode::exit(app_handle);
+    chords::cancel(app_handle);
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    set_paused(
+        app_handle,
+        shortcuts_state,
+        Some(Paused {
+            generation,
+            until: duration.map(|duration| Instant::now() + duration),
+        }),
+    )?;
+
+    match duration {
+        Some(duration) => println!("Paused shortcuts for {} minutes", duration.as_secs() / 60),
+        None => println!("Paused shortcuts until resumed"),
+    }
+    refresh_tray(app_handle);
+
+    if let Some(duration) = duration {
+        let app_handle = app_handle.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let current = PAUSED.lock().unwrap().as_ref().map(|paused| paused.generation);
+            if current == Some(generation) {
+                let shortcuts_state = app_handle.state::<ShortcutManager>().shortcuts.clone();
+                if let Err(e) = resume(&app_handle, &shortcuts_state) {
+                    eprintln!("Failed to resume shortcuts: {}", e);
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Registers every shortcut again after a pause
+pub fn resume(app_handle: &AppHandle, shortcuts_state: &Arc<Mutex<ShortcutsConfig>>) -> Result<(), String> {
+    if !is_paused() {
+        return Ok(());
+    }
+    set_paused(app_handle, shortcuts_state, None)?;
+    println!("Resumed shortcuts");
+    refresh_tray(app_handle);
+    Ok(())
+}
+
+fn refresh_tray(app_handle: &AppHandle) {
+    crate::refresh_tray_menu(app_handle);
+    crate::refresh_tray_tooltip(app_handle);
+    crate::refresh_tray_icon(app_handle);
+}
+
+/// Pauses or resumes from the shortcut handler or the tray menu, which can't
+/// register shortcuts on their own thread. `duration` only applies to pausing.
+pub fn toggle_async(app_handle: &AppHandle, duration: Option<Duration>) {
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        let shortcuts_state = app_handle.state::<ShortcutManager>().shortcuts.clone();
+        let result = if is_paused() {
+            resume(&app_handle, &shortcuts_state)
+        } else {
+            pause(&app_handle, &shortcuts_state, duration)
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to pause or resume shortcuts: {}", e);
+        }
+    });
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseStatus {
+    pub paused: bool,
+    /// Seconds left of a timed pause
+    pub remaining_secs: Option<u64>,
+}
+
+/// Pauses every shortcut but the pause shortcut, for `minutes` if given
+#[tauri::command]
+pub async fn pause_shortcuts(
+    app_handle: AppHandle,
+    minutes: Option<u64>,
+    state: State<'_, ShortcutManager>,
+) -> Result<(), String> {
+    pause(&app_handle, &state.shortcuts, minutes.map(|minutes| Duration::from_secs(minutes * 60)))
+}
+
+/// Registers every shortcut again after a pause
+#[tauri::command]
+pub async fn resume_shortcuts(app_handle: AppHandle, state: State<'_, ShortcutManager>) -> Result<(), String> {
+    resume(&app_handle, &state.shortcuts)
+}
+
+/// Tells whether shortcuts are paused, and for how long
+#[tauri::command]
+pub fn get_pause_status() -> PauseStatus {
+    PauseStatus {
+        paused: is_paused(),
+        remaining_secs: remaining().map(|remaining| remaining.as_secs()),
+    }
+}
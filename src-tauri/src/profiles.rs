@@ -0,0 +1,211 @@
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, State};
+
+use crate::config;
+use crate::shortcuts::{self, ShortcutManager, ShortcutsConfig};
+
+/// Name of the profile made of the top-level settings
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Layout of the settings file: the default profile's settings at the top
+/// level, plus any number of named profiles.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SettingsFile {
+    /// Settings file format version
+    pub version: u32,
+    /// Name of the profile in use; the top-level settings when missing
+    #[serde(rename = "activeProfile", default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    /// Named profiles, each a complete set of settings like the top level
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ShortcutsConfig>,
+    #[serde(flatten)]
+    pub base: ShortcutsConfig,
+}
+
+// Every profile's overrides as last loaded or saved, so saving the active
+// profile doesn't lose the others
+struct ProfileStore {
+    active: String,
+    profiles: BTreeMap<String, ShortcutsConfig>,
+}
+
+static STORE: Lazy<Mutex<ProfileStore>> = Lazy::new(|| {
+    Mutex::new(ProfileStore {
+        active: DEFAULT_PROFILE.to_string(),
+        profiles: BTreeMap::new(),
+    })
+});
+
+// Returns the name of the profile `file` makes active. An unknown active
+// profile falls back to the default.
+fn active_name(file: &SettingsFile) -> String {
+    match &file.active_profile {
+        Some(name) if name == DEFAULT_PROFILE || file.profiles.contains_key(name) => name.clone(),
+        Some(name) => {
+            eprintln!("Unknown active profile '{}', using the default profile", name);
+            DEFAULT_PROFILE.to_string()
+        }
+        None => DEFAULT_PROFILE.to_string(),
+    }
+}
+
+/// Returns the overrides of the profile `file` makes active, without keeping
+/// anything, so the file can be validated before it replaces the stored profiles
+pub fn active_overrides(file: &SettingsFile) -> ShortcutsConfig {
+    match active_name(file).as_str() {
+        DEFAULT_PROFILE => file.base.clone(),
+        name => file.profiles[name].clone(),
+    }
+}
+
+/// Keeps the profiles read from the settings file, and returns the overrides
+/// of the active one. An unknown active profile falls back to the default.
+pub fn store(file: SettingsFile) -> ShortcutsConfig {
+    let active = active_name(&file);
+    let mut store = STORE.lock().unwrap();
+    store.profiles = file.profiles;
+    store.profiles.remove(DEFAULT_PROFILE);
+    store.profiles.insert(DEFAULT_PROFILE.to_string(), file.base);
+    store.active = active;
+    println!("Using profile '{}'", store.active);
+
+    store.profiles[&store.active].clone()
+}
+
+/// Builds the settings file from the stored profiles, with `active` as the
+/// current overrides of the active profile
+pub fn settings_file(active: &ShortcutsConfig) -> SettingsFile {
+    let store = STORE.lock().unwrap();
+    let active_name = store.active.clone();
+
+    let mut profiles = store.profiles.clone();
+    profiles.insert(active_name.clone(), active.clone());
+    let base = profiles
+        .remove(DEFAULT_PROFILE)
+        .unwrap_or_else(|| ShortcutsConfig::default().overrides());
+
+    SettingsFile {
+        version: config::CURRENT_VERSION,
+        active_profile: (active_name != DEFAULT_PROFILE).then_some(active_name),
+        profiles,
+        base,
+    }
+}
+
+/// Records the overrides of the active profile once they've been saved
+pub fn remember(active: ShortcutsConfig) {
+    let mut store = STORE.lock().unwrap();
+    let active_name = store.active.clone();
+    store.profiles.insert(active_name, active);
+}
+
+/// Returns the name of the active profile
+pub fn active() -> String {
+    STORE.lock().unwrap().active.clone()
+}
+
+/// Returns the names of every profile, the default one first
+pub fn names() -> Vec<String> {
+    let store = STORE.lock().unwrap();
+    let mut names = vec![DEFAULT_PROFILE.to_string()];
+    names.extend(store.profiles.keys().filter(|name| *name != DEFAULT_PROFILE).cloned());
+    names
+}
+
+// Returns the stored overrides of the profile `name`
+fn overrides_of(name: &str) -> Result<ShortcutsConfig, String> {
+    STORE
+        .lock()
+        .unwrap()
+        .profiles
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Profile {} not found", name))
+}
+
+/// Makes `name` the active profile before any shortcut is registered, as
+/// the `--profile` command line flag does, and returns its settings
+pub fn activate(name: &str) -> Result<ShortcutsConfig, String> {
+    let config = ShortcutsConfig::from_overrides(overrides_of(name)?);
+    let previous = std::mem::replace(&mut STORE.lock().unwrap().active, name.to_string());
+
+    if let Err(e) = config.save() {
+        STORE.lock().unwrap().active = previous;
+        return Err(e);
+    }
+    println!("Using profile '{}'", name);
+    Ok(config)
+}
+
+/// Switches to the profile `name` and saves the choice.
+///
+/// The shortcuts of the new profile replace the current ones as a single
+/// transaction: if any of them can't be registered, the previous profile
+/// stays active with all its shortcuts.
+pub fn switch_profile(
+    app_handle: &AppHandle,
+    shortcuts_state: &Arc<Mutex<ShortcutsConfig>>,
+    name: &str,
+) -> Result<(), String> {
+    let previous = active();
+    if previous == name {
+        return Ok(());
+    }
+
+    let new_config = ShortcutsConfig::from_overrides(overrides_of(name)?);
+
+    shortcuts::replace_config(app_handle, shortcuts_state, new_config, |config| {
+        STORE.lock().unwrap().active = name.to_string();
+        config.save().inspect_err(|_| {
+            STORE.lock().unwrap().active = previous.clone();
+        })
+    })?;
+
+    println!("Switched from profile '{}' to '{}'", previous, name);
+    crate::refresh_tray_menu(app_handle);
+    crate::refresh_tray_tooltip(app_handle);
+    Ok(())
+}
+
+/// Switches profile from the shortcut handler or the tray menu, which can't
+/// register shortcuts on their own thread
+pub fn switch_profile_async(app_handle: &AppHandle, name: String) {
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        let shortcuts_state = app_handle.state::<ShortcutManager>().shortcuts.clone();
+        if let Err(e) = switch_profile(&app_handle, &shortcuts_state, &name) {
+            eprintln!("Failed to switch to profile '{}': {}", name, e);
+        }
+    });
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileList {
+    pub active: String,
+    pub profiles: Vec<String>,
+}
+
+/// Lists the profiles and which one is active
+#[tauri::command]
+pub fn list_profiles() -> ProfileList {
+    ProfileList {
+        active: active(),
+        profiles: names(),
+    }
+}
+
+/// Switches to another profile
+#[tauri::command]
+pub async fn set_active_profile(
+    app_handle: AppHandle,
+    name: String,
+    state: State<'_, ShortcutManager>,
+) -> Result<(), String> {
+    switch_profile(&app_handle, &state.shortcuts, &name)
+}
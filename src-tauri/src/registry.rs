@@ -0,0 +1,270 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::labels::{self, ShortcutStyle};
+use crate::shortcuts::{ShortcutManager, Trigger};
+use crate::window_actions::*;
+use crate::Action;
+
+/// Describes a single window action that can be bound to a shortcut.
+///
+/// This is the one place an action needs to be declared: the default shortcuts
+/// config, the shortcut dispatch table, the `list_actions` command and
+/// [`Action::get_handler`] are all generated from [`ACTIONS`]. `handler`
+/// creates the action's handler, with any parameters carried by the `action`
+/// value it's given.
+pub struct ActionDescriptor {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub default_shortcut: &'static str,
+    pub action: Action,
+    pub handler: fn(&Action) -> Box<dyn WindowActionHandler>,
+}
+
+// Maximizing takes its gutter from the action value
+fn maximize(action: &Action) -> Box<dyn WindowActionHandler> {
+    let gutter = match action {
+        Action::Maximize { gutter } => *gutter,
+        _ => 0,
+    };
+    Box::new(MaximizeAction { gutter })
+}
+
+// Moving to a monitor takes its number from the action value
+fn move_to_monitor(action: &Action) -> Box<dyn WindowActionHandler> {
+    let number = match action {
+        Action::MoveToMonitor { number } => *number,
+        _ => 0,
+    };
+    Box::new(MoveToMonitorAction { number })
+}
+
+pub static ACTIONS: &[ActionDescriptor] = &[
+    ActionDescriptor {
+        id: "leftHalf",
+        name: "Snap to Left Half",
+        default_shortcut: "Control+Alt+ArrowLeft",
+        action: Action::LeftHalf,
+        handler: |_| Box::new(LeftHalfAction),
+    },
+    ActionDescriptor {
+        id: "rightHalf",
+        name: "Snap to Right Half",
+        default_shortcut: "Control+Alt+ArrowRight",
+        action: Action::RightHalf,
+        handler: |_| Box::new(RightHalfAction),
+    },
+    ActionDescriptor {
+        id: "topHalf",
+        name: "Snap to Top Half",
+        default_shortcut: "Control+Alt+ArrowUp",
+        action: Action::TopHalf,
+        handler: |_| Box::new(TopHalfAction),
+    },
+    ActionDescriptor {
+        id: "bottomHalf",
+        name: "Snap to Bottom Half",
+        default_shortcut: "Control+Alt+ArrowDown",
+        action: Action::BottomHalf,
+        handler: |_| Box::new(BottomHalfAction),
+    },
+    ActionDescriptor {
+        id: "topLeft",
+        name: "Snap to Top Left",
+        default_shortcut: "Control+Alt+U",
+        action: Action::TopLeft,
+        handler: |_| Box::new(TopLeftAction),
+    },
+    ActionDescriptor {
+        id: "topRight",
+        name: "Snap to Top Right",
+        default_shortcut: "Control+Alt+I",
+        action: Action::TopRight,
+        handler: |_| Box::new(TopRightAction),
+    },
+    ActionDescriptor {
+        id: "bottomLeft",
+        name: "Snap to Bottom Left",
+        default_shortcut: "Control+Alt+J",
+        action: Action::BottomLeft,
+        handler: |_| Box::new(BottomLeftAction),
+    },
+    ActionDescriptor {
+        id: "bottomRight",
+        name: "Snap to Bottom Right",
+        default_shortcut: "Control+Alt+K",
+        action: Action::BottomRight,
+        handler: |_| Box::new(BottomRightAction),
+    },
+    ActionDescriptor {
+        id: "firstThird",
+        name: "Snap to First Third",
+        default_shortcut: "Control+Alt+O",
+        action: Action::FirstThird,
+        handler: |_| Box::new(FirstThirdAction),
+    },
+    ActionDescriptor {
+        id: "centerThird",
+        name: "Snap to Center Third",
+        default_shortcut: "Control+Alt+P",
+        action: Action::CenterThird,
+        handler: |_| Box::new(CenterThirdAction),
+    },
+    ActionDescriptor {
+        id: "lastThird",
+        name: "Snap to Last Third",
+        default_shortcut: "Control+Alt+BracketLeft",
+        action: Action::LastThird,
+        handler: |_| Box::new(LastThirdAction),
+    },
+    ActionDescriptor {
+        id: "firstTwoThirds",
+        name: "Snap to First Two Thirds",
+        default_shortcut: "Control+Alt+L",
+        action: Action::FirstTwoThirds,
+        handler: |_| Box::new(FirstTwoThirdsAction),
+    },
+    ActionDescriptor {
+        id: "lastTwoThirds",
+        name: "Snap to Last Two Thirds",
+        default_shortcut: "Control+Alt+Semicolon",
+        action: Action::LastTwoThirds,
+        handler: |_| Box::new(LastTwoThirdsAction),
+    },
+    ActionDescriptor {
+        id: "maximizeWindow",
+        name: "Maximize Window",
+        default_shortcut: "Control+Alt+Enter",
+        action: Action::Maximize { gutter: 0 },
+        handler: maximize,
+    },
+    ActionDescriptor {
+        id: "almostMaximizeWindow",
+        name: "Almost Maximize Window",
+        default_shortcut: "Control+Alt+Shift+Enter",
+        action: Action::Maximize { gutter: 32 },
+        handler: maximize,
+    },
+    ActionDescriptor {
+        id: "maximizeHeight",
+        name: "Maximize Height",
+        default_shortcut: "Control+Alt+Shift+ArrowUp",
+        action: Action::MaximizeHeight,
+        handler: |_| Box::new(MaximizeHeightAction),
+    },
+    ActionDescriptor {
+        id: "center",
+        name: "Center Window",
+        default_shortcut: "Control+Alt+C",
+        action: Action::Center,
+        handler: |_| Box::new(CenterAction),
+    },
+    ActionDescriptor {
+        id: "makeLarger",
+        name: "Make Window Larger",
+        default_shortcut: "Control+Alt+Equal",
+        action: Action::MakeLarger,
+        handler: |_| Box::new(MakeLargerAction),
+    },
+    ActionDescriptor {
+        id: "makeSmaller",
+        name: "Make Window Smaller",
+        default_shortcut: "Control+Alt+Minus",
+        action: Action::MakeSmaller,
+        handler: |_| Box::new(MakeSmallerAction),
+    },
+    ActionDescriptor {
+        id: "moveMonitorLeft",
+        name: "Move to Left Monitor",
+        default_shortcut: "Control+Alt+Shift+ArrowLeft",
+        action: Action::MoveLeft,
+        handler: |_| Box::new(MoveLeftAction),
+    },
+    ActionDescriptor {
+        id: "moveMonitorRight",
+        name: "Move to Right Monitor",
+        default_shortcut: "Control+Alt+Shift+ArrowRight",
+        action: Action::MoveRight,
+        handler: |_| Box::new(MoveRightAction),
+    },
+];
+
+/// Actions that take a parameter only the command line can give, so they have
+/// no shortcut and don't show in the settings
+pub static COMMAND_ACTIONS: &[ActionDescriptor] = &[ActionDescriptor {
+    id: "moveToMonitor",
+    name: "Move to Monitor",
+    default_shortcut: "",
+    action: Action::MoveToMonitor { number: 1 },
+    handler: move_to_monitor,
+}];
+
+/// Creates the handler of `action` through its descriptor; actions without
+/// one do nothing
+pub fn handler(action: &Action) -> Box<dyn WindowActionHandler> {
+    let descriptor = ACTIONS
+        .iter()
+        .chain(COMMAND_ACTIONS)
+        .find(|descriptor| std::mem::discriminant(&descriptor.action) == std::mem::discriminant(action));
+    match descriptor {
+        Some(descriptor) => (descriptor.handler)(action),
+        None => Box::new(NoOpAction),
+    }
+}
+
+/// Looks up an action descriptor by its id
+pub fn find(id: &str) -> Option<&'static ActionDescriptor> {
+    ACTIONS.iter().find(|descriptor| descriptor.id == id)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionInfo {
+    pub id: String,
+    pub name: String,
+    pub default_shortcut: String,
+    pub shortcuts: Vec<String>,
+    /// The shortcuts as shown to the user, see [`labels::format_binding`]
+    pub labels: Vec<String>,
+    pub trigger: Trigger,
+    pub repeat: bool,
+    pub repeatable: bool,
+    /// Whether the shortcuts or trigger differ from the shipped defaults
+    pub modified: bool,
+    pub action: Action,
+}
+
+/// Lists every registered action along with its currently configured shortcuts,
+/// so the frontend can render the settings dynamically.
+#[tauri::command]
+pub async fn list_actions(state: State<'_, ShortcutManager>) -> Result<Vec<ActionInfo>, String> {
+    let config = state.shortcuts.lock().map_err(|e| e.to_string())?;
+
+    Ok(ACTIONS
+        .iter()
+        .map(|descriptor| {
+            let shortcut_cfg = config.get_all_shortcuts().get(descriptor.id);
+            let (trigger, repeat) = shortcut_cfg
+                .map(|cfg| cfg.firing(&descriptor.action))
+                .unwrap_or_default();
+
+            let shortcuts: Vec<String> = shortcut_cfg.map(|cfg| cfg.bindings.clone()).unwrap_or_default();
+
+            ActionInfo {
+                id: descriptor.id.to_string(),
+                name: descriptor.name.to_string(),
+                default_shortcut: descriptor.default_shortcut.to_string(),
+                labels: shortcuts
+                    .iter()
+                    .map(|binding| labels::format_binding(binding, ShortcutStyle::Short))
+                    .collect(),
+                shortcuts,
+                trigger,
+                repeat,
+                repeatable: descriptor.action.is_repeatable(),
+                modified: !config.is_default(descriptor.id),
+                action: descriptor.action.clone(),
+            }
+        })
+        .collect())
+}
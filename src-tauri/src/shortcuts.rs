@@ -1,56 +1,79 @@
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fs;
-use std::str::FromStr;
-use std::sync::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 use tauri::AppHandle;
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
-
-// Helper function to translate symbol keys to their system names
-fn translate_key_symbols(shortcut: &str) -> String {
-    shortcut
-        .replace("=", "Equal")
-        .replace("-", "Minus")
-        .replace("[", "BracketLeft")
-        .replace("]", "BracketRight")
-        .replace(";", "Semicolon")
-        .replace("'", "Quote")
-        .replace(",", "Comma")
-        .replace(".", "Period")
-        .replace("/", "Slash")
-        .replace("\\", "Backslash")
-        .replace("`", "Backquote")
-}
-
-// Helper function to normalize shortcut strings for comparison
-fn normalize_shortcut(shortcut: &str) -> String {
-    let canonical_order = ["shift", "control", "alt", "meta", "super", "cmd", "win"];
-    let mut parts: Vec<&str> = shortcut.split('+').collect();
-    if parts.len() <= 1 {
-        return shortcut.to_lowercase();
-    }
-    let key = parts.pop().unwrap().to_lowercase();
-    let mut key_norm = key.clone();
-    // Normalize key names
-    if let Some(stripped) = key.strip_prefix("key") {
-        key_norm = stripped.to_lowercase();
-    } else if let Some(stripped) = key.strip_prefix("arrow") {
-        key_norm = format!("arrow{}", stripped);
-    }
-    let mut modifiers: Vec<String> = parts.iter().map(|s| s.trim().to_lowercase()).collect();
-    let mut ordered_mods = Vec::new();
-    for &canon in &canonical_order {
-        if let Some(idx) = modifiers.iter().position(|m| m == canon) {
-            ordered_mods.push(canon);
-            modifiers.remove(idx);
-        }
-    }
-    // Add any remaining modifiers (rare/unexpected)
-    ordered_mods.extend(modifiers.iter().map(|s| s.as_str()));
-    let mut normalized = ordered_mods.join("+");
-    normalized.push('+');
-    normalized.push_str(&key_norm);
-    normalized
+use tauri_plugin_global_shortcut::{GlobalShortcut, GlobalShortcutExt, Shortcut};
+
+use crate::autorepeat;
+use crate::chords;
+use crate::config;
+use crate::conflicts;
+use crate::keychord::KeyChord;
+use crate::pause;
+use crate::profiles::{self, SettingsFile};
+use crate::registry;
+use crate::suspend::{self, AutoSuspendConfig};
+use crate::window_layouts::Placement;
+use crate::window_mode::{self, ModeKey, WindowModeConfig, WindowModeKeys};
+use crate::Action;
+
+// Helper function to parse a shortcut string as written in the config
+pub fn parse_shortcut(shortcut: &str) -> Result<Shortcut, String> {
+    KeyChord::parse(shortcut)
+        .map(KeyChord::to_shortcut)
+        .map_err(|e| e.to_string())
+}
+
+/// A configured binding: either a single shortcut, or a leader shortcut
+/// followed by a second key (a chord, written as `"Control+Alt+W H"`).
+///
+/// Only the leader is registered with the OS; the second key is grabbed
+/// while the chord is pending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub leader: Shortcut,
+    pub follow: Option<Shortcut>,
+}
+
+impl Binding {
+    /// Returns true if both bindings can't be told apart when pressed: they
+    /// are identical, or one is a plain shortcut used as the other's leader.
+    pub fn collides_with(&self, other: &Binding) -> bool {
+        self.leader.id() == other.leader.id()
+            && (self.follow.is_none() || other.follow.is_none() || self.follow == other.follow)
+    }
+}
+
+// Helper function to parse a binding, with the chord steps separated by whitespace
+pub fn parse_binding(binding: &str) -> Result<Binding, String> {
+    let mut steps = binding.split_whitespace();
+    let leader = parse_shortcut(steps.next().unwrap_or_default())?;
+    let follow = steps.next().map(parse_shortcut).transpose()?;
+
+    if steps.next().is_some() {
+        return Err(format!(
+            "Invalid shortcut format: {} - chords can only have two steps",
+            binding
+        ));
+    }
+
+    Ok(Binding { leader, follow })
+}
+
+/// Rewrites a binding in canonical form, e.g. `Ctrl+Alt+-` becomes
+/// `Control+Alt+Minus`, so equal bindings are stored the same way
+pub fn canonical_binding(binding: &str) -> Result<String, String> {
+    let steps = binding
+        .split_whitespace()
+        .map(|step| KeyChord::parse(step).map(|chord| chord.to_string()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    parse_binding(binding)?;
+    Ok(steps.join(" "))
 }
 
 // Global state to store the current shortcuts
@@ -68,234 +91,915 @@ impl ShortcutManager {
     }
 }
 
-#[tauri::command]
-pub async fn update_shortcut(
-    app_handle: AppHandle,
-    shortcut_id: String,
-    new_shortcut: String,
-    state: tauri::State<'_, ShortcutManager>,
-) -> Result<(), String> {
-    println!("Updating shortcut {} to {}", shortcut_id, new_shortcut);
+// Serializes every change to the OS registrations. The config mutex itself is
+// never held while registering: the plugin registers on the main thread, which
+// may be waiting on that mutex in a command.
+static REGISTRATION_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
-    // Get the current shortcuts (shared Arc)
-    let mut shortcuts = state.shortcuts.lock().map_err(|e| e.to_string())?;
+/// Holds off every other registration change until the guard is dropped
+pub fn lock_registrations() -> MutexGuard<'static, ()> {
+    REGISTRATION_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
 
-    // Find the shortcut to update
-    if let Some(shortcut_cfg) = shortcuts.shortcuts.get_mut(&shortcut_id) {
-        let old_shortcut_str = shortcut_cfg.default_shortcut.clone();
+/// Returns a copy of the running config to register shortcuts from, without
+/// keeping its mutex locked
+pub fn snapshot(shortcuts_state: &Mutex<ShortcutsConfig>) -> Result<ShortcutsConfig, String> {
+    shortcuts_state.lock().map(|config| config.clone()).map_err(|e| e.to_string())
+}
 
-        // Normalize both shortcuts for comparison
-        let normalized_old = normalize_shortcut(&translate_key_symbols(&old_shortcut_str));
-        let normalized_new = normalize_shortcut(&translate_key_symbols(&new_shortcut));
+/// Runs `change` on a copy of the running config and swaps the copy in if it
+/// succeeds, rebuilding the dispatch table. Other registration changes wait
+/// until it's done, but the config mutex stays free so `change` can register
+/// shortcuts.
+pub fn update_config<T>(
+    shortcuts_state: &Mutex<ShortcutsConfig>,
+    change: impl FnOnce(&mut ShortcutsConfig) -> Result<T, String>,
+) -> Result<T, String> {
+    let _registrations = lock_registrations();
+    let mut config = snapshot(shortcuts_state)?;
+    let result = change(&mut config)?;
+    rebuild_dispatch_table(&config);
+    *shortcuts_state.lock().map_err(|e| e.to_string())? = config;
+    Ok(result)
+}
 
-        println!("Old shortcut: {}", normalized_old);
-        println!("New shortcut: {}", normalized_new);
+/// An action bound to a plain shortcut, along with when it fires
+pub struct BoundAction {
+    pub id: String,
+    pub action: Action,
+    pub trigger: Trigger,
+    pub repeat: bool,
+}
 
-        // Only proceed if the shortcut is actually changing
-        if normalized_old == normalized_new {
-            println!(
-                "Shortcut {} is already set to {}",
-                shortcut_id, new_shortcut
-            );
-            return Ok(());
-        }
+/// What pressing a registered shortcut does
+pub enum Dispatch {
+    /// Triggers an action
+    Action(BoundAction),
+    /// Starts a chord; maps the id of each possible second key to its action
+    Chord(HashMap<u32, (Shortcut, String, Action)>),
+    /// Enters window mode, or leaves it if it's already active
+    WindowMode,
+    /// Switches to the profile with the given name
+    Profile(String),
+    /// Pauses every other shortcut, or resumes them
+    Pause,
+}
 
-        // Update the shortcut in our config first
-        shortcut_cfg.default_shortcut = new_shortcut.clone();
+/// Maps the id of each registered `Shortcut` to what it dispatches
+pub struct DispatchTable {
+    pub bindings: HashMap<u32, Dispatch>,
+    pub chord_timeout: Duration,
+    pub window_mode: WindowModeKeys,
+    pub repeat_delay: Duration,
+    pub repeat_interval: Duration,
+}
 
-        // Parse the new shortcut first to validate it
-        let translated_new_shortcut = translate_key_symbols(&new_shortcut);
-        let new_shortcut_parsed = match Shortcut::from_str(&translated_new_shortcut) {
-            Ok(s) => s,
-            Err(e) => {
-                let error = format!("Invalid shortcut format: {} - {}", new_shortcut, e);
-                println!("{}", error);
-                return Err(error);
-            }
+// Shortcuts whose action already fired on press and are still held. A press
+// swallowed by window mode or a pending chord isn't in here, so its action
+// fires on release instead of being lost.
+static FIRED_ON_PRESS: Lazy<Mutex<std::collections::HashSet<u32>>> =
+    Lazy::new(|| Mutex::new(std::collections::HashSet::new()));
+
+// Precompiled dispatch table, swapped atomically whenever the config changes
+// so the shortcut handler never has to lock or re-parse anything.
+static DISPATCH_TABLE: Lazy<ArcSwap<DispatchTable>> = Lazy::new(|| {
+    ArcSwap::from_pointee(DispatchTable {
+        bindings: HashMap::new(),
+        chord_timeout: Duration::from_millis(DEFAULT_CHORD_TIMEOUT_MS),
+        window_mode: WindowModeConfig::default().resolve().0,
+        repeat_delay: Duration::from_millis(DEFAULT_REPEAT_DELAY_MS),
+        repeat_interval: Duration::from_millis(DEFAULT_REPEAT_INTERVAL_MS),
+    })
+});
+
+// Builds the dispatch table of the given config
+fn build_dispatch_table(config: &ShortcutsConfig) -> DispatchTable {
+    let mut bindings = HashMap::new();
+
+    // Actions are visited in registry order, so when several share a
+    // shortcut the same one wins every time
+    for descriptor in registry::ACTIONS {
+        let Some(shortcut_cfg) = config.shortcuts.get(descriptor.id) else {
+            continue;
         };
+        let (trigger, repeat) = shortcut_cfg.firing(&descriptor.action);
 
-        // Try to register the new shortcut first
-        println!(
-            "Registering new global shortcut: {} for {}",
-            new_shortcut, shortcut_id
-        );
-        if let Err(e) = app_handle.global_shortcut().register(new_shortcut_parsed) {
-            let error = format!("Failed to register new shortcut: {}", e);
-            println!("{}", error);
-            return Err(error);
-        }
-
-        println!("Successfully registered new shortcut: {}", new_shortcut);
-
-        // Try to unregister all possible variations of the old shortcut
-        let old_variants = [&old_shortcut_str, &normalize_shortcut(&old_shortcut_str)];
-        for old_variant in old_variants.iter() {
-            match Shortcut::from_str(old_variant) {
-                Ok(old_shortcut) => {
-                    if let Err(e) = app_handle.global_shortcut().unregister(old_shortcut) {
-                        // Don't fail if unregister fails, just log it
-                        println!(
-                            "Note: Could not unregister old shortcut ({}): {}",
-                            old_shortcut_str, e
-                        );
-                    } else {
-                        println!("Successfully unregistered old shortcut: {}", old_variant);
-                        break;
+        for shortcut in &shortcut_cfg.bindings {
+            // Invalid shortcuts are reported when registering, just leave them out here
+            let Ok(binding) = parse_binding(shortcut) else {
+                continue;
+            };
+
+            match binding.follow {
+                None => {
+                    bindings.entry(binding.leader.id()).or_insert_with(|| {
+                        Dispatch::Action(BoundAction {
+                            id: descriptor.id.to_string(),
+                            action: descriptor.action.clone(),
+                            trigger,
+                            repeat,
+                        })
+                    });
+                }
+                Some(follow) => {
+                    let entry = bindings
+                        .entry(binding.leader.id())
+                        .or_insert_with(|| Dispatch::Chord(HashMap::new()));
+                    if let Dispatch::Chord(keys) = entry {
+                        keys.entry(follow.id())
+                            .or_insert_with(|| (follow, descriptor.id.to_string(), descriptor.action.clone()));
                     }
                 }
-                Err(e) => {
-                    println!(
-                        "Note: Could not parse old shortcut ({}): {}",
-                        old_variant, e
-                    );
+            }
+        }
+    }
+
+    // Actions win over window mode if they share a shortcut
+    if let Some(shortcut) = config.window_mode.activation_shortcut() {
+        bindings.entry(shortcut.id()).or_insert(Dispatch::WindowMode);
+    }
+
+    for (name, shortcut) in &config.profile_shortcuts {
+        if let Ok(shortcut) = parse_shortcut(shortcut) {
+            bindings
+                .entry(shortcut.id())
+                .or_insert_with(|| Dispatch::Profile(name.clone()));
+        }
+    }
+
+    // The pause shortcut wins over everything: while paused, it's the only way back
+    if let Some(shortcut) = config.pause_shortcut() {
+        bindings.insert(shortcut.id(), Dispatch::Pause);
+    }
+
+    // Unresolvable window mode keys are reported when loading, skip them here
+    let (window_mode, _) = config.window_mode.resolve();
+
+    DispatchTable {
+        bindings,
+        chord_timeout: Duration::from_millis(config.chord_timeout_ms),
+        window_mode,
+        repeat_delay: Duration::from_millis(config.repeat_delay_ms),
+        repeat_interval: Duration::from_millis(config.repeat_interval_ms.max(1)),
+    }
+}
+
+/// Rebuilds the dispatch table from the given config and swaps it in
+pub fn rebuild_dispatch_table(config: &ShortcutsConfig) {
+    DISPATCH_TABLE.store(Arc::new(build_dispatch_table(config)));
+}
+
+/// Registers `keys` for a temporary key layer (a pending chord or window mode).
+///
+/// Keys that are already registered, e.g. because they're bound to an action,
+/// are left alone. Returns the keys that were grabbed and must be handed back
+/// to [`release_keys`] afterwards. Must not be called from the shortcut
+/// handler itself, as registering needs the lock the handler runs under.
+pub fn grab_keys(app_handle: &AppHandle, keys: Vec<Shortcut>) -> Vec<Shortcut> {
+    let global_shortcut = app_handle.global_shortcut();
+    keys.into_iter()
+        .filter(|shortcut| !global_shortcut.is_registered(*shortcut))
+        .filter(|shortcut| global_shortcut.register(*shortcut).is_ok())
+        .collect()
+}
+
+/// Unregisters keys previously grabbed with [`grab_keys`]
+pub fn release_keys(app_handle: &AppHandle, keys: Vec<Shortcut>) {
+    for shortcut in keys {
+        let _ = app_handle.global_shortcut().unregister(shortcut);
+    }
+}
+
+/// Registers and unregisters shortcuts with the OS.
+///
+/// Implemented for the global shortcut plugin, and kept as a trait so the
+/// update logic can be exercised without touching real OS shortcuts.
+pub trait ShortcutRegistrar {
+    fn is_registered(&self, shortcut: Shortcut) -> bool;
+    fn register(&self, shortcut: Shortcut) -> Result<(), String>;
+    fn unregister(&self, shortcut: Shortcut) -> Result<(), String>;
+}
+
+impl<R: tauri::Runtime> ShortcutRegistrar for GlobalShortcut<R> {
+    fn is_registered(&self, shortcut: Shortcut) -> bool {
+        GlobalShortcut::is_registered(self, shortcut)
+    }
+
+    fn register(&self, shortcut: Shortcut) -> Result<(), String> {
+        GlobalShortcut::register(self, shortcut).map_err(|e| e.to_string())
+    }
+
+    fn unregister(&self, shortcut: Shortcut) -> Result<(), String> {
+        GlobalShortcut::unregister(self, shortcut).map_err(|e| e.to_string())
+    }
+}
+
+/// A change to the list of shortcuts bound to one action
+pub enum BindingEdit<'a> {
+    /// Replaces the binding at `index`, or appends one if `index` is past the
+    /// end. A combo already bound to another action is refused, unless
+    /// `force_swap` is set, in which case the other action gets the replaced
+    /// binding instead. A combo bound to several other actions can't be
+    /// swapped, as only one of them could take the replaced binding.
+    Set {
+        index: usize,
+        shortcut: &'a str,
+        force_swap: bool,
+    },
+    /// Appends a binding
+    Add { shortcut: &'a str },
+    /// Removes the binding matching `shortcut`
+    Remove { shortcut: &'a str },
+}
+
+/// Changes the bindings of one action as a single transaction.
+///
+/// The steps are: validate the change, register the shortcuts it adds,
+/// unregister the ones nothing uses anymore, update the config and persist
+/// it. If any step fails, every step done so far is undone, so the config,
+/// the OS registrations and the settings file always agree with each other.
+pub fn apply_binding_edit(
+    config: &mut ShortcutsConfig,
+    registrar: &impl ShortcutRegistrar,
+    shortcut_id: &str,
+    edit: BindingEdit,
+    persist: impl FnOnce(&ShortcutsConfig) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut bindings = config
+        .shortcuts
+        .get(shortcut_id)
+        .map(|cfg| cfg.bindings.clone())
+        .ok_or_else(|| format!("Shortcut {} not found", shortcut_id))?;
+    let previous = config.shortcuts.clone();
+
+    match edit {
+        BindingEdit::Set {
+            index,
+            shortcut,
+            force_swap,
+        } => {
+            let new_binding = parse_binding(shortcut)?;
+            let old_shortcut = bindings.get(index).cloned();
+            if old_shortcut
+                .as_deref()
+                .is_some_and(|old| parse_binding(old).ok() == Some(new_binding))
+            {
+                println!("Shortcut {} is already set to {}", shortcut_id, shortcut);
+                return Ok(());
+            }
+
+            validate_new_binding(config, shortcut_id, &bindings, shortcut)?;
+
+            let duplicates = conflicts::find_duplicates(config, shortcut_id, shortcut);
+            if !duplicates.is_empty() {
+                if !force_swap {
+                    return Err(format!(
+                        "Shortcut {} is already used by {}",
+                        shortcut,
+                        duplicates.join(", ")
+                    ));
                 }
+                if duplicates.len() > 1 {
+                    return Err(format!(
+                        "Shortcut {} is used by several actions ({}), unbind all but one to swap",
+                        shortcut,
+                        duplicates.join(", ")
+                    ));
+                }
+                // The other action takes over the binding being replaced, if any
+                for other in &duplicates {
+                    if let Some(other_cfg) = config.shortcuts.get_mut(other) {
+                        let mut taken = Vec::new();
+                        for other_binding in std::mem::take(&mut other_cfg.bindings) {
+                            let collides = parse_binding(&other_binding)
+                                .is_ok_and(|b| b.collides_with(&new_binding));
+                            match (&old_shortcut, collides) {
+                                (Some(old), true) => taken.push(old.clone()),
+                                (None, true) => {}
+                                (_, false) => taken.push(other_binding),
+                            }
+                        }
+                        other_cfg.bindings = taken;
+                    }
+                }
+                println!(
+                    "Swapped shortcut {} ({}) with {} ({})",
+                    shortcut_id,
+                    shortcut,
+                    duplicates.join(", "),
+                    old_shortcut.as_deref().unwrap_or("none")
+                );
+            }
+
+            if index < bindings.len() {
+                bindings[index] = shortcut.to_string();
+            } else {
+                bindings.push(shortcut.to_string());
             }
         }
+        BindingEdit::Add { shortcut } => {
+            parse_binding(shortcut)?;
+            validate_new_binding(config, shortcut_id, &bindings, shortcut)?;
 
-        println!(
-            "Successfully updated shortcut {} from {} to {}",
-            shortcut_id, old_shortcut_str, new_shortcut
-        );
+            let duplicates = conflicts::find_duplicates(config, shortcut_id, shortcut);
+            if !duplicates.is_empty() {
+                return Err(format!(
+                    "Shortcut {} is already used by {}",
+                    shortcut,
+                    duplicates.join(", ")
+                ));
+            }
+            bindings.push(shortcut.to_string());
+        }
+        BindingEdit::Remove { shortcut } => {
+            let removed = parse_binding(shortcut)?;
+            let count = bindings.len();
+            bindings.retain(|binding| parse_binding(binding).ok() != Some(removed));
+            if bindings.len() == count {
+                return Err(format!("Shortcut {} has no binding {}", shortcut_id, shortcut));
+            }
+        }
+    }
 
-        // Save updated shortcuts config to shortcuts.json
-        // Use the same path logic as ShortcutsConfig::load()
-        let frontend_dir = std::env::current_dir().unwrap().parent().unwrap().join("src");
-        let config_path = frontend_dir.join("shortcuts.json");
-        let json = serde_json::to_string_pretty(&*shortcuts)
-            .map_err(|e| format!("Failed to serialize shortcuts: {}", e))?;
-        std::fs::write(&config_path, json)
-            .map_err(|e| format!("Failed to write to {:?}: {}", config_path, e))?;
-        println!("Shortcuts saved to {:?}", config_path);
+    if let Some(shortcut_cfg) = config.shortcuts.get_mut(shortcut_id) {
+        shortcut_cfg.bindings = bindings;
+    }
 
-        Ok(())
-    } else {
-        Err(format!("Shortcut {} not found", shortcut_id))
+    commit_bindings(config, registrar, previous, persist)?;
+    println!(
+        "Successfully updated shortcut {} to [{}]",
+        shortcut_id,
+        config.shortcuts[shortcut_id].bindings.join(", ")
+    );
+
+    Ok(())
+}
+
+/// Restores the shipped shortcuts and trigger of the actions in `shortcut_ids`,
+/// or of every action when `None`, then re-registers and persists like any
+/// other edit. A single action can't take back a default another action is
+/// bound to now.
+pub fn reset_bindings(
+    config: &mut ShortcutsConfig,
+    registrar: &impl ShortcutRegistrar,
+    shortcut_ids: Option<&[&str]>,
+    persist: impl FnOnce(&ShortcutsConfig) -> Result<(), String>,
+) -> Result<(), String> {
+    let defaults = ShortcutsConfig::default();
+    let previous = config.shortcuts.clone();
+
+    let Some(shortcut_ids) = shortcut_ids else {
+        config.shortcuts = defaults.shortcuts;
+        commit_bindings(config, registrar, previous, persist)?;
+        println!("Reset every shortcut to its default");
+        return Ok(());
+    };
+
+    for shortcut_id in shortcut_ids {
+        let default = defaults
+            .shortcuts
+            .get(*shortcut_id)
+            .ok_or_else(|| format!("Shortcut {} not found", shortcut_id))?;
+        for binding in &default.bindings {
+            let duplicates: Vec<String> = conflicts::find_duplicates(config, shortcut_id, binding)
+                .into_iter()
+                .filter(|id| !shortcut_ids.contains(&id.as_str()))
+                .collect();
+            if !duplicates.is_empty() {
+                return Err(format!(
+                    "Default shortcut {} of {} is now used by {}",
+                    binding,
+                    shortcut_id,
+                    duplicates.join(", ")
+                ));
+            }
+        }
+    }
+    for shortcut_id in shortcut_ids {
+        config
+            .shortcuts
+            .insert(shortcut_id.to_string(), defaults.shortcuts[*shortcut_id].clone());
+    }
+
+    commit_bindings(config, registrar, previous, persist)?;
+    println!("Reset {} to the defaults", shortcut_ids.join(", "));
+    Ok(())
+}
+
+// Checks that `shortcut` can be added to an action currently bound to `bindings`
+fn validate_new_binding(
+    config: &ShortcutsConfig,
+    shortcut_id: &str,
+    bindings: &[String],
+    shortcut: &str,
+) -> Result<(), String> {
+    let new_binding = parse_binding(shortcut)?;
+    if bindings
+        .iter()
+        .any(|binding| parse_binding(binding).ok() == Some(new_binding))
+    {
+        return Err(format!("Shortcut {} is already bound to {}", shortcut, shortcut_id));
     }
+    if conflicts::is_reserved(shortcut) {
+        return Err(format!("Shortcut {} is reserved by the system", shortcut));
+    }
+    if conflicts::is_window_mode_shortcut(config, shortcut) {
+        return Err(format!("Shortcut {} is used to enter window mode", shortcut));
+    }
+    if let Some(profile) = conflicts::profile_shortcut(config, shortcut) {
+        return Err(format!("Shortcut {} switches to profile {}", shortcut, profile));
+    }
+    if conflicts::is_pause_shortcut(config, shortcut) {
+        return Err(format!("Shortcut {} pauses the other shortcuts", shortcut));
+    }
+    Ok(())
+}
+
+/// OS registrations made by [`transition_registrations`], kept so they can be undone
+pub struct RegistrationChange {
+    registered: Vec<Shortcut>,
+    released: Vec<Shortcut>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl RegistrationChange {
+    /// Restores the registrations as they were before the change
+    pub fn undo(&self, registrar: &impl ShortcutRegistrar) {
+        for shortcut in &self.released {
+            let _ = registrar.register(*shortcut);
+        }
+        for shortcut in &self.registered {
+            let _ = registrar.unregister(*shortcut);
+        }
+    }
+}
+
+/// Registers the shortcuts in `new` missing from `old`, then unregisters the
+/// ones `new` no longer uses. Either every change is made or none is.
+pub fn transition_registrations(
+    registrar: &impl ShortcutRegistrar,
+    old: &HashMap<u32, Shortcut>,
+    new: &HashMap<u32, Shortcut>,
+) -> Result<RegistrationChange, String> {
+    let mut change = RegistrationChange {
+        registered: Vec::new(),
+        released: Vec::new(),
+    };
+
+    // Step 1: register the new shortcuts
+    for (key, shortcut) in new {
+        if old.contains_key(key) {
+            continue;
+        }
+        if let Err(e) = registrar.register(*shortcut) {
+            change.undo(registrar);
+            return Err(format!("Failed to register new shortcut ({}): {}", shortcut, e));
+        }
+        change.registered.push(*shortcut);
+    }
+
+    // Step 2: unregister the shortcuts nothing uses anymore
+    for (key, shortcut) in old {
+        if new.contains_key(key) || !registrar.is_registered(*shortcut) {
+            continue;
+        }
+        if let Err(e) = registrar.unregister(*shortcut) {
+            change.undo(registrar);
+            return Err(format!("Failed to unregister old shortcut ({}): {}", shortcut, e));
+        }
+        change.released.push(*shortcut);
+    }
+
+    Ok(change)
+}
+
+// Brings the OS registrations in line with the already updated `config`, then
+// persists it. On failure, restores `previous` and the registrations.
+fn commit_bindings(
+    config: &mut ShortcutsConfig,
+    registrar: &impl ShortcutRegistrar,
+    previous: HashMap<String, ShortcutConfig>,
+    persist: impl FnOnce(&ShortcutsConfig) -> Result<(), String>,
+) -> Result<(), String> {
+    // Only leaders are registered with the OS, and several bindings may share
+    // one, so only register and release the ones that appear or disappear
+    let new_leaders = registrations(config);
+    let current = std::mem::replace(&mut config.shortcuts, previous);
+    let old_leaders = registrations(config);
+    let previous = std::mem::replace(&mut config.shortcuts, current);
+
+    let change = match transition_registrations(registrar, &old_leaders, &new_leaders) {
+        Ok(change) => change,
+        Err(e) => {
+            config.shortcuts = previous;
+            return Err(e);
+        }
+    };
+
+    // Step 3: persist the config
+    if let Err(e) = persist(config) {
+        change.undo(registrar);
+        config.shortcuts = previous;
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+// Applies a binding edit from a command and tells the frontend about it
+fn edit_bindings(
+    app_handle: &AppHandle,
+    state: &ShortcutManager,
+    shortcut_id: &str,
+    edit: BindingEdit,
+) -> Result<(), String> {
+    update_config(&state.shortcuts, |shortcuts| {
+        apply_binding_edit(
+            shortcuts,
+            app_handle.global_shortcut(),
+            shortcut_id,
+            edit,
+            ShortcutsConfig::save,
+        )
+        .inspect_err(|e| println!("{}", e))?;
+
+        conflicts::emit_shortcut_status(app_handle, shortcuts);
+        Ok(())
+    })
+}
+
+/// Replaces one binding of an action, the first one unless `index` is given,
+/// and returns the binding as saved, in canonical form
+#[tauri::command]
+pub async fn update_shortcut(
+    app_handle: AppHandle,
+    shortcut_id: String,
+    new_shortcut: String,
+    index: Option<usize>,
+    force_swap: Option<bool>,
+    state: tauri::State<'_, ShortcutManager>,
+) -> Result<String, String> {
+    println!("Updating shortcut {} to {}", shortcut_id, new_shortcut);
+    let new_shortcut = canonical_binding(&new_shortcut)?;
+    edit_bindings(
+        &app_handle,
+        &state,
+        &shortcut_id,
+        BindingEdit::Set {
+            index: index.unwrap_or(0),
+            shortcut: &new_shortcut,
+            force_swap: force_swap.unwrap_or(false),
+        },
+    )?;
+    Ok(new_shortcut)
+}
+
+/// Binds an additional shortcut to an action
+#[tauri::command]
+pub async fn add_binding(
+    app_handle: AppHandle,
+    shortcut_id: String,
+    shortcut: String,
+    state: tauri::State<'_, ShortcutManager>,
+) -> Result<(), String> {
+    println!("Adding shortcut {} to {}", shortcut, shortcut_id);
+    let shortcut = canonical_binding(&shortcut)?;
+    edit_bindings(&app_handle, &state, &shortcut_id, BindingEdit::Add { shortcut: &shortcut })
+}
+
+/// Removes one of the shortcuts bound to an action
+#[tauri::command]
+pub async fn remove_binding(
+    app_handle: AppHandle,
+    shortcut_id: String,
+    shortcut: String,
+    state: tauri::State<'_, ShortcutManager>,
+) -> Result<(), String> {
+    println!("Removing shortcut {} from {}", shortcut, shortcut_id);
+    edit_bindings(&app_handle, &state, &shortcut_id, BindingEdit::Remove { shortcut: &shortcut })
+}
+
+/// Sets whether an action fires on press or on release, and whether it
+/// auto-repeats while held. `None` restores the action's default.
+#[tauri::command]
+pub async fn update_action_trigger(
+    shortcut_id: String,
+    trigger: Option<Trigger>,
+    repeat: Option<bool>,
+    state: tauri::State<'_, ShortcutManager>,
+) -> Result<(), String> {
+    // The change is made on a copy, so nothing needs undoing if saving fails
+    update_config(&state.shortcuts, |shortcuts| {
+        let shortcut_cfg = shortcuts
+            .shortcuts
+            .get_mut(&shortcut_id)
+            .ok_or_else(|| format!("Shortcut {} not found", shortcut_id))?;
+        shortcut_cfg.trigger = trigger;
+        shortcut_cfg.repeat = repeat;
+        shortcuts.save()
+    })?;
+
+    println!("Updated trigger of {} to {:?} (repeat: {:?})", shortcut_id, trigger, repeat);
+    Ok(())
+}
+
+// Resets actions from a command and tells the frontend about it
+fn reset(app_handle: &AppHandle, state: &ShortcutManager, shortcut_ids: Option<&[&str]>) -> Result<(), String> {
+    update_config(&state.shortcuts, |shortcuts| {
+        reset_bindings(shortcuts, app_handle.global_shortcut(), shortcut_ids, ShortcutsConfig::save)
+            .inspect_err(|e| println!("{}", e))?;
+
+        conflicts::emit_shortcut_status(app_handle, shortcuts);
+        Ok(())
+    })
+}
+
+/// Restores the shipped shortcuts and trigger of one action
+#[tauri::command]
+pub async fn reset_shortcut(
+    app_handle: AppHandle,
+    shortcut_id: String,
+    state: tauri::State<'_, ShortcutManager>,
+) -> Result<(), String> {
+    println!("Resetting shortcut {}", shortcut_id);
+    reset(&app_handle, &state, Some(&[shortcut_id.as_str()]))
+}
+
+/// Restores the shipped shortcuts and trigger of every action
+#[tauri::command]
+pub async fn reset_all_shortcuts(app_handle: AppHandle, state: tauri::State<'_, ShortcutManager>) -> Result<(), String> {
+    println!("Resetting every shortcut");
+    reset(&app_handle, &state, None)
+}
+
+/// When a shortcut fires its action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Trigger {
+    /// As soon as the shortcut is pressed
+    Press,
+    /// Once the shortcut is released
+    #[default]
+    Release,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
 pub struct ShortcutConfig {
     pub name: String,
-    #[serde(rename = "defaultShortcut")]
-    pub default_shortcut: String,
+    /// Every shortcut that triggers the action; empty leaves it unbound
+    pub bindings: Vec<String>,
+    /// Whether the action fires on press or on release, defaults to release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<Trigger>,
+    /// Whether the action repeats while its shortcut is held. Only resizing
+    /// actions can repeat, and they do by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<bool>,
+}
+
+impl ShortcutConfig {
+    /// Returns when `action` fires and whether it auto-repeats
+    pub fn firing(&self, action: &Action) -> (Trigger, bool) {
+        let repeat = action.is_repeatable() && self.repeat.unwrap_or(true);
+        // Repeating starts as soon as the shortcut is pressed
+        let trigger = if repeat {
+            Trigger::Press
+        } else {
+            self.trigger.unwrap_or_default()
+        };
+        (trigger, repeat)
+    }
 }
 
 impl Default for ShortcutConfig {
     fn default() -> Self {
         Self {
             name: String::new(),
-            default_shortcut: String::new(),
+            bindings: Vec::new(),
+            trigger: None,
+            repeat: None,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Galaxy user settings, making up one profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShortcutsConfig {
+    #[serde(default)]
     pub shortcuts: HashMap<String, ShortcutConfig>,
+    /// How long a chord waits for its second key, in milliseconds
+    #[serde(rename = "chordTimeoutMs", default = "default_chord_timeout_ms")]
+    pub chord_timeout_ms: u64,
+    /// Modal window mode, in which plain keys perform actions
+    #[serde(rename = "windowMode", default)]
+    pub window_mode: WindowModeConfig,
+    /// How long a repeating shortcut is held before it starts repeating, in milliseconds
+    #[serde(rename = "repeatDelayMs", default = "default_repeat_delay_ms")]
+    pub repeat_delay_ms: u64,
+    /// Time between repeats while a repeating shortcut is held, in milliseconds
+    #[serde(rename = "repeatIntervalMs", default = "default_repeat_interval_ms")]
+    pub repeat_interval_ms: u64,
+    /// Shortcuts that switch to another profile, keyed by profile name
+    #[serde(rename = "profileShortcuts", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profile_shortcuts: BTreeMap<String, String>,
+    /// Shortcut that pauses every other shortcut and resumes them, leave empty to disable it
+    #[serde(rename = "pauseShortcut", default = "default_pause_shortcut")]
+    pub pause_shortcut: String,
+    /// When every shortcut is suspended automatically, depending on the focused window
+    #[serde(rename = "autoSuspend", default)]
+    pub auto_suspend: AutoSuspendConfig,
+    /// Window layouts to apply with `galaxy layout apply <name>`, keyed by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub layouts: BTreeMap<String, Vec<Placement>>,
 }
 
-impl Default for ShortcutsConfig {
-    fn default() -> Self {
-        let mut shortcuts = HashMap::new();
-
-        // Add default shortcuts
-        shortcuts.insert(
-            "moveMonitorLeft".to_string(),
-            ShortcutConfig {
-                name: "Move to Left Monitor".to_string(),
-                default_shortcut: "Shift+Control+Alt+ArrowLeft".to_string(),
-            },
-        );
+// Returns true if `cfg` is what `defaults` ships for the action
+fn is_default(defaults: &ShortcutsConfig, shortcut_id: &str, cfg: &ShortcutConfig) -> bool {
+    defaults.shortcuts.get(shortcut_id) == Some(cfg)
+}
 
-        shortcuts.insert(
-            "moveMonitorRight".to_string(),
-            ShortcutConfig {
-                name: "Move to Right Monitor".to_string(),
-                default_shortcut: "Shift+Control+Alt+ArrowRight".to_string(),
-            },
-        );
+const DEFAULT_CHORD_TIMEOUT_MS: u64 = 1500;
 
-        shortcuts.insert(
-            "maximizeWindow".to_string(),
-            ShortcutConfig {
-                name: "Maximize Window".to_string(),
-                default_shortcut: "Control+Alt+Enter".to_string(),
-            },
-        );
+fn default_chord_timeout_ms() -> u64 {
+    DEFAULT_CHORD_TIMEOUT_MS
+}
 
-        shortcuts.insert(
-            "almostMaximizeWindow".to_string(),
-            ShortcutConfig {
-                name: "Almost Maximize Window".to_string(),
-                default_shortcut: "Shift+Control+Alt+Enter".to_string(),
-            },
-        );
+const DEFAULT_REPEAT_DELAY_MS: u64 = 400;
+const DEFAULT_REPEAT_INTERVAL_MS: u64 = 60;
+
+fn default_repeat_delay_ms() -> u64 {
+    DEFAULT_REPEAT_DELAY_MS
+}
 
-        Self { shortcuts }
+fn default_repeat_interval_ms() -> u64 {
+    DEFAULT_REPEAT_INTERVAL_MS
+}
+
+const DEFAULT_PAUSE_SHORTCUT: &str = "Control+Alt+Shift+P";
+
+fn default_pause_shortcut() -> String {
+    DEFAULT_PAUSE_SHORTCUT.to_string()
+}
+
+impl Default for ShortcutsConfig {
+    fn default() -> Self {
+        // Defaults come straight from the action registry
+        let shortcuts = registry::ACTIONS
+            .iter()
+            .map(|descriptor| {
+                (
+                    descriptor.id.to_string(),
+                    ShortcutConfig {
+                        name: descriptor.name.to_string(),
+                        bindings: vec![descriptor.default_shortcut.to_string()],
+                        trigger: None,
+                        repeat: None,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            shortcuts,
+            chord_timeout_ms: DEFAULT_CHORD_TIMEOUT_MS,
+            window_mode: WindowModeConfig::default(),
+            repeat_delay_ms: DEFAULT_REPEAT_DELAY_MS,
+            repeat_interval_ms: DEFAULT_REPEAT_INTERVAL_MS,
+            profile_shortcuts: BTreeMap::new(),
+            pause_shortcut: default_pause_shortcut(),
+            auto_suspend: AutoSuspendConfig::default(),
+            layouts: BTreeMap::new(),
+        }
     }
 }
 
 impl ShortcutsConfig {
+    /// Loads the shipped defaults and applies the active profile's overrides
+    /// from the settings file on top of them.
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        println!("Loading shortcuts from bundled resource...");
-        
-        // Try to load from bundled resource first (production)
-        match Self::load_from_resource() {
-            Ok(config) => {
-                println!("Successfully loaded shortcuts from bundled resource: {} shortcuts", config.shortcuts.len());
-                return Ok(config);
-            }
-            Err(e) => {
-                println!("Failed to load from bundled resource: {}", e);
-                println!("Falling back to file system (development mode)...");
-            }
-        }
-        
-        // Fallback to file system (development)
-        Self::load_from_file()
-    }
-    
-    fn load_from_resource() -> Result<Self, Box<dyn std::error::Error>> {
-        // In production, the file is bundled as a resource
-        let config_content = include_str!("../../src/shortcuts.json");
-        
-        let parsed: ShortcutsConfig = serde_json::from_str(config_content)
-            .map_err(|e| format!("Failed to parse bundled shortcuts.json: {}", e))?;
-            
-        Ok(parsed)
+        let Some(file) = Self::read_settings()? else {
+            return Ok(Self::default());
+        };
+        let overrides = profiles::store(file);
+
+        println!("Successfully parsed {} shortcut overrides", overrides.shortcuts.len());
+
+        Ok(Self::from_overrides(overrides))
     }
-    
-    fn load_from_file() -> Result<Self, Box<dyn std::error::Error>> {
-        // Get the path to the shortcuts.json file in the frontend directory
-        let frontend_dir = std::env::current_dir()?.parent().unwrap().join("src");
-        let config_path = frontend_dir.join("shortcuts.json");
 
-        println!("Loading shortcuts from: {:?}", config_path);
+    /// Reads and upgrades the settings file without applying it. Returns
+    /// `None` when there is no file.
+    pub fn read_settings() -> Result<Option<SettingsFile>, Box<dyn std::error::Error>> {
+        let config_path = config::config_path();
 
-        let config_content = fs::read_to_string(&config_path).map_err(|e| {
-            format!(
-                "Failed to read shortcuts.json: {}\nPath: {:?}",
-                e, config_path
-            )
-        })?;
+        if !config_path.exists() {
+            println!("No user settings at {:?}, using defaults", config_path);
+            return Ok(None);
+        }
 
-        println!("Raw JSON content: {}", config_content);
+        println!("Loading shortcuts from: {:?}", config_path);
+        Ok(Some(config::load_from(&config_path)?))
+    }
 
-        let parsed: ShortcutsConfig = serde_json::from_str(&config_content)
-            .map_err(|e| format!("Failed to parse shortcuts.json: {}", e))?;
+    /// Applies a profile's overrides on top of the shipped defaults
+    pub fn from_overrides(overrides: ShortcutsConfig) -> Self {
+        let mut config = Self::default();
+        config.shortcuts.extend(overrides.shortcuts);
+        // Bindings written by hand or by older versions are compared in canonical form
+        for shortcut_cfg in config.shortcuts.values_mut() {
+            for binding in &mut shortcut_cfg.bindings {
+                if let Ok(canonical) = canonical_binding(binding) {
+                    *binding = canonical;
+                }
+            }
+        }
+        config.chord_timeout_ms = overrides.chord_timeout_ms;
+        config.window_mode = overrides.window_mode;
+        config.repeat_delay_ms = overrides.repeat_delay_ms;
+        config.repeat_interval_ms = overrides.repeat_interval_ms;
+        config.profile_shortcuts = overrides.profile_shortcuts;
+        config.pause_shortcut = overrides.pause_shortcut;
+        config.auto_suspend = overrides.auto_suspend;
+        config.layouts = overrides.layouts;
+        config
+    }
+
+    /// Returns the JSON Schema of the settings file, for editor validation
+    pub fn json_schema() -> String {
+        let schema = schemars::schema_for!(SettingsFile);
+        serde_json::to_string_pretty(&schema).unwrap_or_default()
+    }
 
-        println!("Successfully parsed {} shortcuts", parsed.shortcuts.len());
+    /// Returns only the shortcuts that differ from the shipped defaults
+    pub fn overrides(&self) -> Self {
+        let defaults = Self::default();
+        let shortcuts = self
+            .shortcuts
+            .iter()
+            .filter(|(id, cfg)| !is_default(&defaults, id, cfg))
+            .map(|(id, cfg)| (id.clone(), cfg.clone()))
+            .collect();
 
-        Ok(parsed)
+        Self {
+            shortcuts,
+            chord_timeout_ms: self.chord_timeout_ms,
+            window_mode: self.window_mode.clone(),
+            repeat_delay_ms: self.repeat_delay_ms,
+            repeat_interval_ms: self.repeat_interval_ms,
+            profile_shortcuts: self.profile_shortcuts.clone(),
+            pause_shortcut: self.pause_shortcut.clone(),
+            auto_suspend: self.auto_suspend.clone(),
+            layouts: self.layouts.clone(),
+        }
     }
 
+    /// Atomically writes the user's overrides to the active profile in the settings file
+    pub fn save(&self) -> Result<(), String> {
+        let config_path = config::config_path();
+        let overrides = self.overrides();
+        let json = serde_json::to_string_pretty(&profiles::settings_file(&overrides))
+            .map_err(|e| format!("Failed to serialize shortcuts: {}", e))?;
+        config::write_atomic(&config_path, &json)?;
+        profiles::remember(overrides);
+        println!("Shortcuts saved to {:?}", config_path);
+        Ok(())
+    }
 
     pub fn get_all_shortcuts(&self) -> &HashMap<String, ShortcutConfig> {
         &self.shortcuts
     }
+
+    /// Returns true if the action's shortcuts and trigger are the shipped defaults
+    pub fn is_default(&self, shortcut_id: &str) -> bool {
+        self.shortcuts
+            .get(shortcut_id)
+            .is_some_and(|cfg| is_default(&Self::default(), shortcut_id, cfg))
+    }
+
+    /// Iterates over every binding of every action, as `(action id, binding)`
+    pub fn bindings(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.shortcuts
+            .iter()
+            .flat_map(|(id, cfg)| cfg.bindings.iter().map(move |binding| (id, binding)))
+    }
+
+    /// Parses the shortcut that pauses and resumes the others; `None` if
+    /// it's disabled or invalid
+    pub fn pause_shortcut(&self) -> Option<Shortcut> {
+        let shortcut = self.pause_shortcut.trim();
+        (!shortcut.is_empty()).then(|| parse_shortcut(shortcut).ok()).flatten()
+    }
 }
 
 
+pub fn trigger_action(shortcut_id: &str, action: &Action, shortcut: &Shortcut, started: Instant) {
+    println!("Triggering {} action for shortcut: {}", shortcut_id, shortcut);
+    let _ = super::move_window(Some(action.clone()));
+
+    if super::DEBUG_MODE.load(std::sync::atomic::Ordering::Relaxed) {
+        println!("Handled {} in {:?}", shortcut_id, started.elapsed());
+    }
+}
+
 pub fn register_shortcuts(
     app: &mut tauri::App,
     shortcuts_state: Arc<Mutex<ShortcutsConfig>>,
@@ -308,76 +1012,96 @@ pub fn register_shortcuts(
         println!("Loaded shortcuts from config:");
         for (id, shortcut_cfg) in config.get_all_shortcuts() {
             println!(
-                "  {}: {} = {}",
-                id, shortcut_cfg.name, shortcut_cfg.default_shortcut
+                "  {}: {} = [{}]",
+                id, shortcut_cfg.name, shortcut_cfg.bindings.join(", ")
             );
         }
     } // lock released here
     let app_handle = app.handle();
 
+    rebuild_dispatch_table(&shortcuts_state.lock().unwrap());
+
     // Register the plugin
-    let handler_shortcuts_state = shortcuts_state.clone();
     let plugin = tauri_plugin_global_shortcut::Builder::new()
-        .with_handler(move |_app, shortcut, event| {
-            use super::Action;
+        .with_handler(move |app, shortcut, event| {
             use tauri_plugin_global_shortcut::ShortcutState;
 
-            let shortcut_str = shortcut.to_string();
-            let normalized_shortcut = normalize_shortcut(&shortcut_str);
-            
-            println!(
-                "Shortcut event: {} (normalized: {}) - {:?}",
-                shortcut_str, normalized_shortcut, event.state()
-            );
+            let started = Instant::now();
+
+            if event.state() == ShortcutState::Pressed {
+                // Key layers only act on release, and nothing does while paused.
+                // A swallowed press-triggered action fires on release instead.
+                if window_mode::is_active() || chords::is_pending() || pause::is_paused() {
+                    return;
+                }
+
+                let table = DISPATCH_TABLE.load();
+                if let Some(Dispatch::Action(bound)) = table.bindings.get(&shortcut.id()) {
+                    if bound.trigger == Trigger::Press {
+                        FIRED_ON_PRESS.lock().unwrap().insert(shortcut.id());
+                        trigger_action(&bound.id, &bound.action, shortcut, started);
+                        if bound.repeat {
+                            autorepeat::start(
+                                *shortcut,
+                                bound.id.clone(),
+                                bound.action.clone(),
+                                table.repeat_delay,
+                                table.repeat_interval,
+                            );
+                        }
+                    }
+                }
+            }
 
             if event.state() == ShortcutState::Released {
-                // Lock and get the latest shortcuts mapping
-                let config = handler_shortcuts_state.lock().unwrap();
-                
-                // Create a mapping of normalized shortcuts to actions
-                let mut shortcut_to_action = std::collections::HashMap::new();
-                
-                for (id, shortcut_cfg) in config.get_all_shortcuts() {
-                    let normalized = normalize_shortcut(&translate_key_symbols(&shortcut_cfg.default_shortcut));
-                    let action = match id.as_str() {
-                        "moveMonitorLeft" => Some(Action::MoveLeft),
-                        "moveMonitorRight" => Some(Action::MoveRight),
-                        "maximizeWindow" => Some(Action::Maximize { gutter: 0 }),
-                        "almostMaximizeWindow" => Some(Action::Maximize { gutter: 32 }),
-                        "leftHalf" => Some(Action::LeftHalf),
-                        "rightHalf" => Some(Action::RightHalf),
-                        "topHalf" => Some(Action::TopHalf),
-                        "bottomHalf" => Some(Action::BottomHalf),
-                        "topLeft" => Some(Action::TopLeft),
-                        "topRight" => Some(Action::TopRight),
-                        "bottomLeft" => Some(Action::BottomLeft),
-                        "bottomRight" => Some(Action::BottomRight),
-                        "firstThird" => Some(Action::FirstThird),
-                        "centerThird" => Some(Action::CenterThird),
-                        "lastThird" => Some(Action::LastThird),
-                        "firstTwoThirds" => Some(Action::FirstTwoThirds),
-                        "lastTwoThirds" => Some(Action::LastTwoThirds),
-                        "center" => Some(Action::Center),
-                        "makeLarger" => Some(Action::MakeLarger),
-                        "makeSmaller" => Some(Action::MakeSmaller),
-                        "maximizeHeight" => Some(Action::MaximizeHeight),
-                        _ => None,
-                    };
-                    
-                    if let Some(action) = action {
-                        shortcut_to_action.insert(normalized, (id.clone(), action));
+                autorepeat::stop(shortcut.id());
+                let fired_on_press = FIRED_ON_PRESS.lock().unwrap().remove(&shortcut.id());
+
+                // Window mode keys take precedence while it's active
+                match window_mode::handle_key(app, shortcut.id()) {
+                    Some(ModeKey::Action(shortcut_id, action)) => {
+                        trigger_action(&shortcut_id, &action, shortcut, started);
+                        return;
                     }
+                    Some(ModeKey::Exit) => return,
+                    None => {}
                 }
 
-                
-                println!("Available shortcuts: {:?}", shortcut_to_action.keys().collect::<Vec<_>>());
+                // Any key ends a pending chord; its second keys trigger their action
+                if let Some((shortcut_id, action)) = chords::finish(app, shortcut.id()) {
+                    trigger_action(&shortcut_id, &action, shortcut, started);
+                    return;
+                }
 
                 // Look up the action for this shortcut
-                if let Some((shortcut_id, action)) = shortcut_to_action.get(&normalized_shortcut) {
-                    println!("Triggering {} action for shortcut: {}", shortcut_id, shortcut_str);
-                    let _ = super::move_window(Some(action.clone()));
-                } else {
-                    println!("No action found for shortcut: {} (normalized: {})", shortcut_str, normalized_shortcut);
+                let table = DISPATCH_TABLE.load();
+                match table.bindings.get(&shortcut.id()) {
+                    Some(Dispatch::Action(bound)) => {
+                        // Actions that fire on press already did, unless a key
+                        // layer swallowed the press
+                        if bound.trigger == Trigger::Release || (!fired_on_press && !pause::is_paused()) {
+                            trigger_action(&bound.id, &bound.action, shortcut, started);
+                        }
+                    }
+                    Some(Dispatch::Chord(keys)) => {
+                        chords::begin(app, shortcut, keys, table.chord_timeout);
+                    }
+                    Some(Dispatch::WindowMode) => {
+                        if window_mode::is_active() {
+                            window_mode::exit(app);
+                        } else {
+                            window_mode::enter(app, &table.window_mode);
+                        }
+                    }
+                    Some(Dispatch::Profile(name)) => {
+                        profiles::switch_profile_async(app, name.clone());
+                    }
+                    Some(Dispatch::Pause) => {
+                        pause::toggle_async(app, None);
+                    }
+                    None => {
+                        println!("No action found for shortcut: {}", shortcut);
+                    }
                 }
             }
         })
@@ -394,25 +1118,28 @@ pub fn register_shortcuts(
         let mut registered_shortcuts = std::collections::HashSet::new();
         let mut errors = Vec::new();
         
-        for (id, shortcut_cfg) in config.get_all_shortcuts() {
-            let translated_shortcut = translate_key_symbols(&shortcut_cfg.default_shortcut);
-            match Shortcut::from_str(&translated_shortcut) {
-                Ok(shortcut) => {
+        for (id, binding_str) in config.bindings() {
+            match parse_binding(binding_str) {
+                Ok(binding) => {
+                    // Only a chord's leader is registered, its second key is grabbed on demand
+                    let shortcut = binding.leader;
                     let shortcut_str = shortcut.to_string();
                     
                     // Check if this exact shortcut was already registered
                     if registered_shortcuts.contains(&shortcut_str) {
-                        let warning = format!(
-                            "Warning: Duplicate shortcut '{}' for '{}' - skipping",
-                            shortcut_str, id
-                        );
-                        println!("{}", warning);
+                        if binding.follow.is_none() {
+                            let warning = format!(
+                                "Warning: Duplicate shortcut '{}' for '{}' - skipping",
+                                shortcut_str, id
+                            );
+                            println!("{}", warning);
+                        }
                         continue;
                     }
                     
                     println!(
                         "Registering global shortcut: {} for {}",
-                        shortcut_cfg.default_shortcut, id
+                        binding_str, id
                     );
                     
                     // Use catch_unwind to handle any panics during registration
@@ -427,7 +1154,7 @@ pub fn register_shortcuts(
                         Ok(Err(e)) => {
                             let error_msg = format!(
                                 "Failed to register shortcut '{}' for '{}': {}",
-                                shortcut_cfg.default_shortcut, id, e
+                                binding_str, id, e
                             );
                             eprintln!("{}", error_msg);
                             errors.push(error_msg);
@@ -435,7 +1162,7 @@ pub fn register_shortcuts(
                         Err(_) => {
                             let error_msg = format!(
                                 "Panic while registering shortcut '{}' for '{}' (likely already registered by another application)",
-                                shortcut_cfg.default_shortcut, id
+                                binding_str, id
                             );
                             eprintln!("{}", error_msg);
                             errors.push(error_msg);
@@ -445,7 +1172,7 @@ pub fn register_shortcuts(
                 Err(e) => {
                     let error_msg = format!(
                         "Invalid shortcut format for '{}' (value: '{}'): {}",
-                        id, shortcut_cfg.default_shortcut, e
+                        id, binding_str, e
                     );
                     eprintln!("{}", error_msg);
                     errors.push(error_msg);
@@ -453,6 +1180,87 @@ pub fn register_shortcuts(
             }
         }
         
+        // The window mode shortcut is registered like any other, unless an action already uses it
+        match config.window_mode.parse() {
+            Ok(Some(shortcut)) if !registered_shortcuts.contains(&shortcut.to_string()) => {
+                println!("Registering window mode shortcut: {}", config.window_mode.shortcut);
+                match app_handle.global_shortcut().register(shortcut) {
+                    Ok(_) => {
+                        registered_shortcuts.insert(shortcut.to_string());
+                    }
+                    Err(e) => {
+                        let error_msg = format!(
+                            "Failed to register window mode shortcut '{}': {}",
+                            config.window_mode.shortcut, e
+                        );
+                        eprintln!("{}", error_msg);
+                        errors.push(error_msg);
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("{}", e);
+                errors.push(e);
+            }
+        }
+
+        // Profile switching shortcuts too
+        for (name, shortcut_str) in &config.profile_shortcuts {
+            match parse_shortcut(shortcut_str) {
+                Ok(shortcut) if !registered_shortcuts.contains(&shortcut.to_string()) => {
+                    println!("Registering shortcut {} for profile {}", shortcut_str, name);
+                    match app_handle.global_shortcut().register(shortcut) {
+                        Ok(_) => {
+                            registered_shortcuts.insert(shortcut.to_string());
+                        }
+                        Err(e) => {
+                            let error_msg = format!(
+                                "Failed to register shortcut '{}' for profile '{}': {}",
+                                shortcut_str, name, e
+                            );
+                            eprintln!("{}", error_msg);
+                            errors.push(error_msg);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    let error_msg = format!("Invalid shortcut for profile '{}': {}", name, e);
+                    eprintln!("{}", error_msg);
+                    errors.push(error_msg);
+                }
+            }
+        }
+
+        // And the pause shortcut
+        if !config.pause_shortcut.trim().is_empty() {
+            match parse_shortcut(config.pause_shortcut.trim()) {
+                Ok(shortcut) if !registered_shortcuts.contains(&shortcut.to_string()) => {
+                    println!("Registering pause shortcut: {}", config.pause_shortcut);
+                    match app_handle.global_shortcut().register(shortcut) {
+                        Ok(_) => {
+                            registered_shortcuts.insert(shortcut.to_string());
+                        }
+                        Err(e) => {
+                            let error_msg = format!(
+                                "Failed to register pause shortcut '{}': {}",
+                                config.pause_shortcut, e
+                            );
+                            eprintln!("{}", error_msg);
+                            errors.push(error_msg);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    let error_msg = format!("Invalid pause shortcut: {}", e);
+                    eprintln!("{}", error_msg);
+                    errors.push(error_msg);
+                }
+            }
+        }
+
         // Handle any registration errors
         if !errors.is_empty() {
             let error_message = format!(
@@ -470,7 +1278,425 @@ pub fn register_shortcuts(
             let error_msg = "Failed to register any shortcuts. All shortcut registrations failed.";
             return Err(error_msg.to_string());
         }
+
+        conflicts::emit_shortcut_status(app_handle, &config);
+    }
+
+    Ok(())
+}
+
+/// Parses every shortcut in the config, returning the ones to register with
+/// the OS (chord leaders included) keyed by their id, along with an error
+/// message for each invalid one
+fn parse_shortcuts(config: &ShortcutsConfig) -> (HashMap<u32, Shortcut>, Vec<String>) {
+    let mut parsed = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (id, shortcut) in config.bindings() {
+        match parse_binding(shortcut) {
+            Ok(binding) => {
+                parsed.insert(binding.leader.id(), binding.leader);
+            }
+            Err(e) => errors.push(format!(
+                "Invalid shortcut format for '{}' (value: '{}'): {}",
+                id, shortcut, e
+            )),
+        }
+    }
+
+    match config.window_mode.parse() {
+        Ok(Some(shortcut)) => {
+            parsed.insert(shortcut.id(), shortcut);
+        }
+        Ok(None) => {}
+        Err(e) => errors.push(e),
+    }
+    let (_, key_errors) = config.window_mode.resolve();
+    errors.extend(key_errors);
+
+    for (name, shortcut) in &config.profile_shortcuts {
+        match parse_shortcut(shortcut) {
+            Ok(shortcut) => {
+                parsed.insert(shortcut.id(), shortcut);
+            }
+            Err(e) => errors.push(format!("Invalid shortcut for profile '{}': {}", name, e)),
+        }
     }
 
+    if !config.pause_shortcut.trim().is_empty() {
+        match parse_shortcut(config.pause_shortcut.trim()) {
+            Ok(shortcut) => {
+                parsed.insert(shortcut.id(), shortcut);
+            }
+            Err(e) => errors.push(format!("Invalid pause shortcut: {}", e)),
+        }
+    }
+
+    (parsed, errors)
+}
+
+/// Returns the shortcuts to register with the OS for `config`, keyed by
+/// their id: every valid one, only the pause shortcut while paused, or none
+/// while suspended
+pub fn registrations(config: &ShortcutsConfig) -> HashMap<u32, Shortcut> {
+    if suspend::is_suspended() {
+        return HashMap::new();
+    }
+    let (parsed, _) = parse_shortcuts(config);
+    pause::filter_registrations(config, parsed)
+}
+
+/// Checks that every shortcut in `config` can be parsed
+pub fn validate_config(config: &ShortcutsConfig) -> Result<(), String> {
+    let (_, errors) = parse_shortcuts(config);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// Replaces the running configuration with `new_config` as a single transaction.
+///
+/// Every shortcut is validated before anything changes. Only the shortcuts
+/// that were added or removed are registered/unregistered with the OS, and if
+/// any of that or `persist` fails, the previous configuration stays active
+/// with all its registrations and the error is returned.
+pub fn replace_config(
+    app_handle: &AppHandle,
+    shortcuts_state: &Arc<Mutex<ShortcutsConfig>>,
+    new_config: ShortcutsConfig,
+    persist: impl FnOnce(&ShortcutsConfig) -> Result<(), String>,
+) -> Result<(), String> {
+    validate_config(&new_config)?;
+
+    update_config(shortcuts_state, |config| {
+        let old_shortcuts = registrations(config);
+        let new_shortcuts = registrations(&new_config);
+
+        // Key layers may have grabbed keys the new config binds differently
+        window_mode::exit(app_handle);
+        chords::cancel(app_handle);
+
+        let registrar = app_handle.global_shortcut();
+        let change = transition_registrations(registrar, &old_shortcuts, &new_shortcuts)?;
+        if let Err(e) = persist(&new_config) {
+            change.undo(registrar);
+            return Err(e);
+        }
+
+        conflicts::emit_shortcut_status(app_handle, &new_config);
+        *config = new_config;
+        Ok(())
+    })
+}
+
+/// Re-reads the settings file and applies it to the running app.
+///
+/// If the file can't be loaded or contains an invalid shortcut, the current
+/// configuration and profiles stay active and the error is returned. The
+/// profiles read from the file are only kept once its shortcuts are registered.
+pub fn reload_shortcuts(
+    app_handle: &AppHandle,
+    shortcuts_state: &Arc<Mutex<ShortcutsConfig>>,
+) -> Result<(), String> {
+    let settings = ShortcutsConfig::read_settings().map_err(|e| e.to_string())?;
+    let new_config = match &settings {
+        Some(file) => ShortcutsConfig::from_overrides(profiles::active_overrides(file)),
+        None => ShortcutsConfig::default(),
+    };
+
+    replace_config(app_handle, shortcuts_state, new_config, |_| {
+        if let Some(file) = settings {
+            profiles::store(file);
+        }
+        Ok(())
+    })?;
+
+    crate::refresh_tray_menu(app_handle);
+    crate::refresh_tray_tooltip(app_handle);
+    println!("Reloaded shortcuts from settings file");
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActionContext, MonitorInfo, WindowInfo};
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashSet;
+
+    // Keeps registrations in memory, and fails registering or unregistering
+    // one chosen shortcut
+    #[derive(Default)]
+    struct MockRegistrar {
+        registered: RefCell<HashSet<u32>>,
+        fail_register: Option<Shortcut>,
+        fail_unregister: Option<Shortcut>,
+    }
+
+    impl MockRegistrar {
+        // Starts out with every shortcut of `config` registered
+        fn registered_for(config: &ShortcutsConfig) -> Self {
+            Self {
+                registered: RefCell::new(registrations(config).into_keys().collect()),
+                ..Self::default()
+            }
+        }
+
+        fn snapshot(&self) -> HashSet<u32> {
+            self.registered.borrow().clone()
+        }
+    }
+
+    impl ShortcutRegistrar for MockRegistrar {
+        fn is_registered(&self, shortcut: Shortcut) -> bool {
+            self.registered.borrow().contains(&shortcut.id())
+        }
+
+        fn register(&self, shortcut: Shortcut) -> Result<(), String> {
+            if self.fail_register == Some(shortcut) {
+                return Err("registration refused".to_string());
+            }
+            if !self.registered.borrow_mut().insert(shortcut.id()) {
+                return Err("already registered".to_string());
+            }
+            Ok(())
+        }
+
+        fn unregister(&self, shortcut: Shortcut) -> Result<(), String> {
+            if self.fail_unregister == Some(shortcut) {
+                return Err("unregistration refused".to_string());
+            }
+            if !self.registered.borrow_mut().remove(&shortcut.id()) {
+                return Err("not registered".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    const OLD: &str = "Control+Alt+ArrowLeft";
+    const NEW: &str = "Control+Alt+Shift+F9";
+
+    fn shortcut(text: &str) -> Shortcut {
+        parse_shortcut(text).unwrap()
+    }
+
+    // Replaces leftHalf's default binding with an unused one
+    fn rebind_left_half(
+        config: &mut ShortcutsConfig,
+        registrar: &MockRegistrar,
+        persist: impl FnOnce(&ShortcutsConfig) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let edit = BindingEdit::Set {
+            index: 0,
+            shortcut: NEW,
+            force_swap: false,
+        };
+        apply_binding_edit(config, registrar, "leftHalf", edit, persist)
+    }
+
+    #[test]
+    fn edit_registers_the_new_binding_and_releases_the_old_one() {
+        let mut config = ShortcutsConfig::default();
+        let registrar = MockRegistrar::registered_for(&config);
+        let persisted = Cell::new(false);
+
+        rebind_left_half(&mut config, &registrar, |_| {
+            persisted.set(true);
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(persisted.get());
+        assert_eq!(config.shortcuts["leftHalf"].bindings, [NEW]);
+        assert!(registrar.is_registered(shortcut(NEW)));
+        assert!(!registrar.is_registered(shortcut(OLD)));
+    }
+
+    #[test]
+    fn failed_register_changes_nothing() {
+        let mut config = ShortcutsConfig::default();
+        let registrar = MockRegistrar {
+            fail_register: Some(shortcut(NEW)),
+            ..MockRegistrar::registered_for(&config)
+        };
+        let (before, registered) = (config.shortcuts.clone(), registrar.snapshot());
+
+        let result = rebind_left_half(&mut config, &registrar, |_| panic!("persisted a failed edit"));
+
+        assert!(result.is_err());
+        assert_eq!(config.shortcuts, before);
+        assert_eq!(registrar.snapshot(), registered);
+    }
+
+    #[test]
+    fn failed_unregister_releases_the_new_binding() {
+        let mut config = ShortcutsConfig::default();
+        let registrar = MockRegistrar {
+            fail_unregister: Some(shortcut(OLD)),
+            ..MockRegistrar::registered_for(&config)
+        };
+        let (before, registered) = (config.shortcuts.clone(), registrar.snapshot());
+
+        let result = rebind_left_half(&mut config, &registrar, |_| panic!("persisted a failed edit"));
+
+        assert!(result.is_err());
+        assert_eq!(config.shortcuts, before);
+        assert_eq!(registrar.snapshot(), registered);
+        assert!(!registrar.is_registered(shortcut(NEW)));
+    }
+
+    #[test]
+    fn failed_persist_restores_both_registrations() {
+        let mut config = ShortcutsConfig::default();
+        let registrar = MockRegistrar::registered_for(&config);
+        let (before, registered) = (config.shortcuts.clone(), registrar.snapshot());
+
+        let result = rebind_left_half(&mut config, &registrar, |_| Err("disk full".to_string()));
+
+        assert_eq!(result, Err("disk full".to_string()));
+        assert_eq!(config.shortcuts, before);
+        assert_eq!(registrar.snapshot(), registered);
+        assert!(registrar.is_registered(shortcut(OLD)));
+    }
+
+    #[test]
+    fn failed_persist_undoes_a_reset() {
+        let mut config = ShortcutsConfig::default();
+        config.shortcuts.get_mut("leftHalf").unwrap().bindings = vec![NEW.to_string()];
+        let registrar = MockRegistrar::registered_for(&config);
+        let (before, registered) = (config.shortcuts.clone(), registrar.snapshot());
+
+        let result = reset_bindings(&mut config, &registrar, None, |_| Err("disk full".to_string()));
+
+        assert!(result.is_err());
+        assert_eq!(config.shortcuts, before);
+        assert_eq!(registrar.snapshot(), registered);
+    }
+
+    #[test]
+    fn swap_with_several_actions_is_refused() {
+        let mut config = ShortcutsConfig::default();
+        for id in ["rightHalf", "topHalf"] {
+            config.shortcuts.get_mut(id).unwrap().bindings.push(NEW.to_string());
+        }
+        let registrar = MockRegistrar::registered_for(&config);
+        let (before, registered) = (config.shortcuts.clone(), registrar.snapshot());
+
+        let edit = BindingEdit::Set {
+            index: 0,
+            shortcut: NEW,
+            force_swap: true,
+        };
+        let error = apply_binding_edit(&mut config, &registrar, "leftHalf", edit, |_| {
+            panic!("persisted a refused swap")
+        })
+        .unwrap_err();
+
+        assert!(error.contains("rightHalf") && error.contains("topHalf"), "{error}");
+        assert_eq!(config.shortcuts, before);
+        assert_eq!(registrar.snapshot(), registered);
+    }
+
+    #[test]
+    fn shared_shortcuts_go_to_the_first_action_in_the_registry() {
+        let shortcut = registry::find("leftHalf").unwrap().default_shortcut;
+        let leader = parse_shortcut(shortcut).unwrap();
+
+        // Each config hashes its actions in a different order
+        for _ in 0..20 {
+            let mut config = ShortcutsConfig::default();
+            for id in ["rightHalf", "maximizeWindow", "center"] {
+                config.shortcuts.get_mut(id).unwrap().bindings = vec![shortcut.to_string()];
+            }
+            let table = build_dispatch_table(&config);
+            let Some(Dispatch::Action(bound)) = table.bindings.get(&leader.id()) else {
+                panic!("{} is not bound to an action", shortcut);
+            };
+            assert_eq!(bound.id, "leftHalf");
+        }
+    }
+
+    #[test]
+    fn failed_register_undoes_the_ones_before_it() {
+        let registrar = MockRegistrar {
+            fail_register: Some(shortcut("Control+Alt+Shift+F10")),
+            ..MockRegistrar::default()
+        };
+        let old = HashMap::new();
+        let new: HashMap<u32, Shortcut> = ["Control+Alt+Shift+F9", "Control+Alt+Shift+F10", "Control+Alt+Shift+F11"]
+            .into_iter()
+            .map(|text| (shortcut(text).id(), shortcut(text)))
+            .collect();
+
+        assert!(transition_registrations(&registrar, &old, &new).is_err());
+        assert!(registrar.snapshot().is_empty());
+    }
+
+    #[test]
+    fn undo_restores_the_registrations_before_the_change() {
+        let registrar = MockRegistrar::default();
+        let old: HashMap<u32, Shortcut> = [(shortcut(OLD).id(), shortcut(OLD))].into();
+        let new: HashMap<u32, Shortcut> = [(shortcut(NEW).id(), shortcut(NEW))].into();
+        registrar.register(shortcut(OLD)).unwrap();
+
+        let change = transition_registrations(&registrar, &old, &new).unwrap();
+        assert_eq!(registrar.snapshot(), new.keys().copied().collect());
+
+        change.undo(&registrar);
+        assert_eq!(registrar.snapshot(), old.keys().copied().collect());
+    }
+
+    // Everything between a keypress and SetWindowPos: the table lookup, the
+    // handler and its position calculation. Timing only, so it's ignored by
+    // default; run it with `cargo test dispatch_latency -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn dispatch_latency() {
+        const PRESSES: u32 = 10_000;
+
+        rebuild_dispatch_table(&ShortcutsConfig::default());
+        let shortcut = parse_shortcut(registry::find("leftHalf").unwrap().default_shortcut).unwrap();
+        let monitor = MonitorInfo {
+            left: 0,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+            work_left: 0,
+            work_top: 0,
+            work_right: 1920,
+            work_bottom: 1040,
+            dpi: 96,
+            scale_factor: 1.0,
+        };
+        let context = ActionContext {
+            current_monitor: 0,
+            target_monitor: 0,
+            monitors: vec![monitor],
+            window_info: WindowInfo {
+                current_width: 800,
+                current_height: 600,
+                center_x: 960,
+                center_y: 520,
+            },
+        };
+
+        let started = Instant::now();
+        for _ in 0..PRESSES {
+            let table = DISPATCH_TABLE.load();
+            let Some(Dispatch::Action(bound)) = table.bindings.get(&shortcut.id()) else {
+                panic!("leftHalf is not in the dispatch table");
+            };
+            let handler = bound.action.get_handler();
+            let target = handler.get_target_monitor(context.current_monitor, &context.monitors);
+            assert_eq!(target, 0);
+            handler.calculate_position(&context, std::ptr::null_mut()).unwrap();
+        }
+        let per_press = started.elapsed() / PRESSES;
+
+        println!("Dispatch takes {:?} per keypress", per_press);
+        assert!(per_press < Duration::from_micros(100), "dispatch took {:?}", per_press);
+    }
+}
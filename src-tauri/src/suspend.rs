@@ -0,0 +1,236 @@
+use once_cell::sync::{Lazy, OnceCell};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::ptr::null_mut;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::GlobalShortcutExt;
+use winapi::shared::minwindef::{DWORD, FALSE};
+use winapi::shared::ntdef::LONG;
+use winapi::shared::windef::{HWINEVENTHOOK, HWND, RECT};
+use winapi::um::handleapi::CloseHandle;
+use winapi::um::processthreadsapi::OpenProcess;
+use winapi::um::winbase::QueryFullProcessImageNameW;
+use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+use winapi::um::winuser::{
+    DispatchMessageW, GetClassNameW, GetDesktopWindow, GetForegroundWindow, GetMessageW, GetMonitorInfoW,
+    GetShellWindow, GetWindowLongW, GetWindowRect, GetWindowThreadProcessId, MonitorFromWindow, SetWinEventHook,
+    TranslateMessage, EVENT_OBJECT_LOCATIONCHANGE, EVENT_SYSTEM_FOREGROUND, GWL_STYLE, MONITORINFO,
+    MONITOR_DEFAULTTONEAREST, MSG, OBJID_WINDOW, WINEVENT_OUTOFCONTEXT, WS_CAPTION, WS_MAXIMIZE,
+};
+
+use crate::chords;
+use crate::shortcuts::{self, ShortcutsConfig};
+use crate::window_mode;
+
+// Window classes of the desktop and the taskbars, which cover a whole
+// monitor or sit on top of it without being fullscreen applications
+const SHELL_CLASSES: &[&str] = &["WorkerW", "Progman", "Shell_TrayWnd", "Shell_SecondaryTrayWnd"];
+
+/// When shortcuts are suspended automatically, so the focused application
+/// gets every key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoSuspendConfig {
+    /// Suspend while a fullscreen window is focused, e.g. a game or a presentation
+    pub fullscreen: bool,
+    /// Executable names that suspend shortcuts while focused, e.g. `mstsc.exe`
+    pub processes: Vec<String>,
+}
+
+impl Default for AutoSuspendConfig {
+    fn default() -> Self {
+        Self {
+            fullscreen: true,
+            // Remote desktop and Hyper-V clients forward shortcuts to the remote machine
+            processes: vec!["mstsc.exe".to_string(), "vmconnect.exe".to_string()],
+        }
+    }
+}
+
+// What suspended the shortcuts, while they're suspended
+static SUSPENDED: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Returns true while shortcuts are suspended
+pub fn is_suspended() -> bool {
+    SUSPENDED.lock().unwrap().is_some()
+}
+
+/// Returns what suspended the shortcuts, e.g. `mstsc.exe`
+pub fn reason() -> Option<String> {
+    SUSPENDED.lock().unwrap().clone()
+}
+
+/// Returns the executable name of the process owning `hwnd`
+pub unsafe fn process_name(hwnd: HWND) -> Option<String> {
+    let mut process_id = 0;
+    GetWindowThreadProcessId(hwnd, &mut process_id);
+    let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, process_id);
+    if process.is_null() {
+        return None;
+    }
+
+    let mut buffer = [0u16; 1024];
+    let mut len = buffer.len() as u32;
+    let ok = QueryFullProcessImageNameW(process, 0, buffer.as_mut_ptr(), &mut len);
+    CloseHandle(process);
+    if ok == 0 {
+        return None;
+    }
+
+    let path = String::from_utf16_lossy(&buffer[..len as usize]);
+    Path::new(&path).file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+// Returns true if `hwnd` covers its whole monitor, taskbar included. The
+// desktop itself covers it too but doesn't count, and neither do maximized
+// or captioned windows, which match the monitor when the taskbar auto-hides.
+unsafe fn is_fullscreen(hwnd: HWND) -> bool {
+    if hwnd == GetDesktopWindow() || hwnd == GetShellWindow() {
+        return false;
+    }
+    let mut class = [0u16; 64];
+    let len = GetClassNameW(hwnd, class.as_mut_ptr(), class.len() as i32);
+    let class = String::from_utf16_lossy(&class[..len.max(0) as usize]);
+    if SHELL_CLASSES.contains(&class.as_str()) {
+        return false;
+    }
+
+    let style = GetWindowLongW(hwnd, GWL_STYLE) as u32;
+    if style & WS_MAXIMIZE != 0 || style & WS_CAPTION == WS_CAPTION {
+        return false;
+    }
+
+    let mut window: RECT = std::mem::zeroed();
+    if GetWindowRect(hwnd, &mut window) == 0 {
+        return false;
+    }
+    let mut monitor: MONITORINFO = std::mem::zeroed();
+    monitor.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+    if GetMonitorInfoW(MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST), &mut monitor) == 0 {
+        return false;
+    }
+
+    let screen = monitor.rcMonitor;
+    window.left <= screen.left && window.top <= screen.top && window.right >= screen.right && window.bottom >= screen.bottom
+}
+
+/// Returns why the focused window `hwnd` should suspend shortcuts, if it should
+fn suspend_reason(config: &AutoSuspendConfig, hwnd: HWND) -> Option<String> {
+    unsafe {
+        if !config.processes.is_empty() {
+            if let Some(name) = process_name(hwnd) {
+                if config.processes.iter().any(|process| process.eq_ignore_ascii_case(&name)) {
+                    return Some(name);
+                }
+            }
+        }
+        if config.fullscreen && is_fullscreen(hwnd) {
+            return Some("a fullscreen window".to_string());
+        }
+        None
+    }
+}
+
+// Suspends or restores the shortcuts and brings the registrations in line,
+// as a single transaction
+fn set_suspended(
+    app_handle: &AppHandle,
+    shortcuts_state: &Arc<Mutex<ShortcutsConfig>>,
+    reason: Option<String>,
+) -> Result<(), String> {
+    if reason.is_some() {
+        // Key layers grab keys of their own, leave them first
+        window_mode::exit(app_handle);
+        chords::cancel(app_handle);
+    }
+
+    let _registrations = shortcuts::lock_registrations();
+    let config = shortcuts::snapshot(shortcuts_state)?;
+    let before = shortcuts::registrations(&config);
+    let previous = std::mem::replace(&mut *SUSPENDED.lock().unwrap(), reason);
+    let after = shortcuts::registrations(&config);
+
+    if let Err(e) = shortcuts::transition_registrations(app_handle.global_shortcut(), &before, &after) {
+        *SUSPENDED.lock().unwrap() = previous;
+        return Err(e);
+    }
+    Ok(())
+}
+
+// What the foreground hook works on, set once by `watch_foreground`
+static WATCHED: OnceCell<(AppHandle, Arc<Mutex<ShortcutsConfig>>)> = OnceCell::new();
+
+// Suspends or restores the shortcuts for the focused window `hwnd`, if that
+// changes anything
+fn update(app_handle: &AppHandle, shortcuts_state: &Arc<Mutex<ShortcutsConfig>>, hwnd: HWND) {
+    let Ok(config) = shortcuts_state.lock().map(|config| config.auto_suspend.clone()) else {
+        return;
+    };
+    let reason = suspend_reason(&config, hwnd);
+    if reason == self::reason() {
+        return;
+    }
+
+    match &reason {
+        Some(reason) => println!("Suspending shortcuts while {} is focused", reason),
+        None => println!("Focus moved on, registering shortcuts again"),
+    }
+    match set_suspended(app_handle, shortcuts_state, reason) {
+        Ok(()) => crate::refresh_tray_tooltip(app_handle),
+        Err(e) => eprintln!("Failed to suspend or restore shortcuts: {}", e),
+    }
+}
+
+// Called by Windows when the focus moves, and when a window moves or resizes,
+// which is how the focused window entering or leaving fullscreen shows up
+unsafe extern "system" fn on_win_event(
+    _hook: HWINEVENTHOOK,
+    _event: DWORD,
+    hwnd: HWND,
+    object: LONG,
+    _child: LONG,
+    _thread: DWORD,
+    _time: DWORD,
+) {
+    if object != OBJID_WINDOW || hwnd.is_null() || hwnd != GetForegroundWindow() {
+        return;
+    }
+    if let Some((app_handle, shortcuts_state)) = WATCHED.get() {
+        update(app_handle, shortcuts_state, hwnd);
+    }
+}
+
+/// Watches the focused window and suspends every shortcut while it's
+/// fullscreen or belongs to one of the configured processes, registering
+/// them again once focus moves on.
+pub fn watch_foreground(app_handle: AppHandle, shortcuts_state: Arc<Mutex<ShortcutsConfig>>) {
+    if WATCHED.set((app_handle, shortcuts_state)).is_err() {
+        return;
+    }
+
+    std::thread::spawn(|| unsafe {
+        // The window focused at startup gets no focus event
+        if let Some((app_handle, shortcuts_state)) = WATCHED.get() {
+            let hwnd = GetForegroundWindow();
+            if !hwnd.is_null() {
+                update(app_handle, shortcuts_state, hwnd);
+            }
+        }
+
+        // Out-of-context hooks are delivered through this thread's message loop
+        for event in [EVENT_SYSTEM_FOREGROUND, EVENT_OBJECT_LOCATIONCHANGE] {
+            let hook = SetWinEventHook(event, event, null_mut(), Some(on_win_event), 0, 0, WINEVENT_OUTOFCONTEXT);
+            if hook.is_null() {
+                eprintln!("Failed to watch the focused window (event {:#x})", event);
+            }
+        }
+
+        let mut msg: MSG = std::mem::zeroed();
+        while GetMessageW(&mut msg, null_mut(), 0, 0) > 0 {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+}
@@ -0,0 +1,70 @@
+use notify::{RecursiveMode, Watcher};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::config;
+use crate::shortcuts::{self, ShortcutsConfig};
+
+// Editors often write a file in several steps, wait for them to settle
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Watches the settings file and hot-reloads shortcuts whenever it changes.
+///
+/// The parent directory is watched rather than the file itself, since saving
+/// atomically replaces the file. Saves made by Galaxy itself are skipped, as
+/// they're already applied. Failed reloads keep the previous configuration
+/// and are reported through the `config-error` event.
+pub fn watch_config(app_handle: AppHandle, shortcuts_state: Arc<Mutex<ShortcutsConfig>>) {
+    std::thread::spawn(move || {
+        let config_path = config::config_path();
+        let Some(watch_dir) = config_path.parent().map(|dir| dir.to_path_buf()) else {
+            eprintln!("Invalid settings path, not watching: {:?}", config_path);
+            return;
+        };
+        let _ = std::fs::create_dir_all(&watch_dir);
+
+        let (tx, rx) = mpsc::channel();
+        // The watcher lives on this thread's stack for as long as the app runs
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Failed to create settings watcher: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&watch_dir, RecursiveMode::NonRecursive) {
+            eprintln!("Failed to watch {:?}: {}", watch_dir, e);
+            return;
+        }
+
+        let touches_config = |event: &notify::Result<notify::Event>| match event {
+            Ok(event) => event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == config_path.file_name()),
+            Err(_) => false,
+        };
+
+        while let Ok(event) = rx.recv() {
+            if !touches_config(&event) {
+                continue;
+            }
+
+            // Swallow the rest of this burst of events
+            while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+            // Galaxy's own saves are already applied
+            if config::is_own_write(&config_path) {
+                continue;
+            }
+
+            println!("Settings file changed, reloading...");
+            if let Err(e) = shortcuts::reload_shortcuts(&app_handle, &shortcuts_state) {
+                eprintln!("Failed to reload settings, keeping previous configuration: {}", e);
+                let _ = app_handle.emit("config-error", e);
+            }
+        }
+    });
+}
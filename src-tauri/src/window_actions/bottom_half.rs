@@ -1,4 +1,5 @@
 use crate::ActionContext;
+use crate::window_actions::geometry::tile;
 use crate::window_actions::WindowActionHandler;
 use winapi::shared::windef::HWND;
 
@@ -11,10 +12,9 @@ impl WindowActionHandler for BottomHalfAction {
         let target_work_height = target_monitor_info.work_bottom - target_monitor_info.work_top;
 
         // Snap to bottom half of the monitor
+        let (new_y, new_height) = tile(target_monitor_info.work_top, target_work_height, 2, 1, 1);
         let new_width = target_work_width;
-        let new_height = target_work_height / 2;
         let new_x = target_monitor_info.work_left;
-        let new_y = target_monitor_info.work_top + target_work_height / 2;
 
         println!("Snapping to bottom half: {}x{} at ({},{})", new_width, new_height, new_x, new_y);
         Ok((new_width, new_height, new_x, new_y))
@@ -0,0 +1,327 @@
+use crate::{ActionContext, MonitorInfo};
+
+/// Returns the offset of the boundary between division `index - 1` and `index`
+/// when `length` is split into `parts` divisions.
+///
+/// Boundaries are computed from the total length rather than by adding up
+/// truncated division sizes, so the last boundary always lands exactly on
+/// `length` and any rounding remainder is spread across the divisions.
+pub fn edge(length: i32, parts: i32, index: i32) -> i32 {
+    ((length as i64 * index as i64) / parts as i64) as i32
+}
+
+/// Returns `(offset, size)` of a tile covering `span` consecutive divisions
+/// starting at division `index` of a `length`-long axis that begins at `start`.
+///
+/// Adjacent tiles share their boundary exactly, so complementary snaps such as
+/// left half / right half or first third / last two thirds never leave a gap
+/// or overlap, whatever the work area size.
+pub fn tile(start: i32, length: i32, parts: i32, index: i32, span: i32) -> (i32, i32) {
+    let from = edge(length, parts, index);
+    let to = edge(length, parts, index + span);
+    (start + from, to - from)
+}
+
+/// Picks the monitor a window rectangle belongs to.
+///
+/// The monitor sharing the largest intersection area with the window wins.
+/// When the window overlaps no monitor at all (e.g. it sits in the dead space
+/// between monitors of different heights), the monitor closest to the window
+/// centre is used instead. Returns `None` only when `monitors` is empty.
+pub fn current_monitor(left: i32, top: i32, right: i32, bottom: i32, monitors: &[MonitorInfo]) -> Option<usize> {
+    let overlap = |m: &MonitorInfo| -> i64 {
+        let width = (right.min(m.right) - left.max(m.left)).max(0) as i64;
+        let height = (bottom.min(m.bottom) - top.max(m.top)).max(0) as i64;
+        width * height
+    };
+
+    let best_overlap = monitors
+        .iter()
+        .enumerate()
+        .map(|(idx, m)| (idx, overlap(m)))
+        .filter(|(_, area)| *area > 0)
+        .max_by(|(a_idx, a), (b_idx, b)| a.cmp(b).then(b_idx.cmp(a_idx)))
+        .map(|(idx, _)| idx);
+
+    if best_overlap.is_some() {
+        return best_overlap;
+    }
+
+    // No overlap: fall back to the monitor nearest to the window centre
+    let center_x = (left as i64 + right as i64) / 2;
+    let center_y = (top as i64 + bottom as i64) / 2;
+    let distance = |m: &MonitorInfo| -> i64 {
+        let dx = (m.left as i64 - center_x).max(center_x - (m.right as i64 - 1)).max(0);
+        let dy = (m.top as i64 - center_y).max(center_y - (m.bottom as i64 - 1)).max(0);
+        dx * dx + dy * dy
+    };
+
+    monitors
+        .iter()
+        .enumerate()
+        .min_by_key(|(idx, m)| (distance(m), *idx))
+        .map(|(idx, _)| idx)
+}
+
+/// Returns `(width, height, x, y)` of the window carried from its monitor to
+/// the target monitor: the same logical size, at the same relative position
+/// within the work area, and kept inside it.
+pub fn carry_over(context: &ActionContext) -> (i32, i32, i32, i32) {
+    let target_monitor_info = &context.monitors[context.target_monitor];
+    let current_monitor_info = &context.monitors[context.current_monitor];
+
+    // Calculate work area dimensions
+    let current_work_width = current_monitor_info.work_right - current_monitor_info.work_left;
+    let current_work_height = current_monitor_info.work_bottom - current_monitor_info.work_top;
+    let target_work_width = target_monitor_info.work_right - target_monitor_info.work_left;
+    let target_work_height = target_monitor_info.work_bottom - target_monitor_info.work_top;
+
+    // Calculate relative position within current monitor work area
+    let relative_x = (context.window_info.center_x - current_monitor_info.work_left) as f32 / current_work_width as f32;
+    let relative_y = (context.window_info.center_y - current_monitor_info.work_top) as f32 / current_work_height as f32;
+
+    // Keep the same logical size, converting between the two monitors' DPI
+    let dpi_ratio = target_monitor_info.scale_factor / current_monitor_info.scale_factor;
+    let new_width = ((context.window_info.current_width as f32 * dpi_ratio).round() as i32).min(target_work_width);
+    let new_height = ((context.window_info.current_height as f32 * dpi_ratio).round() as i32).min(target_work_height);
+
+    // Calculate new center position
+    let new_center_x = target_monitor_info.work_left + (target_work_width as f32 * relative_x) as i32;
+    let new_center_y = target_monitor_info.work_top + (target_work_height as f32 * relative_y) as i32;
+
+    // Calculate final window position ensuring it stays within work area bounds
+    let new_x = (new_center_x - new_width / 2)
+        .max(target_monitor_info.work_left)
+        .min(target_monitor_info.work_right - new_width);
+    let new_y = (new_center_y - new_height / 2)
+        .max(target_monitor_info.work_top)
+        .min(target_monitor_info.work_bottom - new_height);
+
+    (new_width, new_height, new_x, new_y)
+}
+
+/// Returns the indices of `monitors` in the order they're numbered for
+/// people, left to right and then top to bottom, so monitor 1 is the leftmost
+pub fn numbered(monitors: &[MonitorInfo]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..monitors.len()).collect();
+    order.sort_by_key(|idx| (monitors[*idx].left, monitors[*idx].top));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WindowInfo;
+
+    // Work area lengths to check: every small one, plus a few large ones
+    fn lengths() -> impl Iterator<Item = i32> {
+        (1..=4000).chain([5119, 5120, 7679, 7680, 32767])
+    }
+
+    // Work areas may start anywhere in the virtual screen, including left of
+    // or above the primary monitor
+    const STARTS: [i32; 3] = [0, -1921, 1366];
+
+    #[test]
+    fn edges_start_and_end_on_the_axis() {
+        for length in lengths() {
+            for parts in [2, 3] {
+                assert_eq!(edge(length, parts, 0), 0);
+                assert_eq!(edge(length, parts, parts), length);
+            }
+        }
+    }
+
+    #[test]
+    fn neighbouring_tiles_share_an_edge() {
+        for length in lengths() {
+            for parts in [2, 3] {
+                for start in STARTS {
+                    for index in 0..parts {
+                        for span in 1..=parts - index {
+                            let (offset, size) = tile(start, length, parts, index, span);
+                            assert!(size >= 0, "negative size for {length}/{parts} at {index}+{span}");
+                            if index + span < parts {
+                                let (next, _) = tile(start, length, parts, index + span, 1);
+                                assert_eq!(offset + size, next, "gap or overlap for {length}/{parts} at {index}+{span}");
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn complementary_tiles_cover_the_axis_exactly() {
+        for length in lengths() {
+            for parts in [2, 3] {
+                for start in STARTS {
+                    // Halves, and a third next to two thirds either way round
+                    for split in 1..parts {
+                        let (first, first_size) = tile(start, length, parts, 0, split);
+                        let (second, second_size) = tile(start, length, parts, split, parts - split);
+                        assert_eq!(first, start);
+                        assert_eq!(first + first_size, second);
+                        assert_eq!(second + second_size, start + length);
+                    }
+
+                    // Every single division in a row
+                    let mut position = start;
+                    for index in 0..parts {
+                        let (offset, size) = tile(start, length, parts, index, 1);
+                        assert_eq!(offset, position);
+                        position += size;
+                    }
+                    assert_eq!(position, start + length);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn divisions_differ_by_at_most_one_pixel() {
+        for length in lengths() {
+            for parts in [2, 3] {
+                let sizes: Vec<i32> = (0..parts).map(|index| tile(0, length, parts, index, 1).1).collect();
+                let (min, max) = (sizes.iter().min().unwrap(), sizes.iter().max().unwrap());
+                assert!(max - min <= 1, "uneven divisions {sizes:?} of {length}");
+            }
+        }
+    }
+
+    fn monitor(left: i32, top: i32, right: i32, bottom: i32) -> MonitorInfo {
+        MonitorInfo {
+            left,
+            top,
+            right,
+            bottom,
+            work_left: left,
+            work_top: top,
+            work_right: right,
+            work_bottom: bottom,
+            dpi: 96,
+            scale_factor: 1.0,
+        }
+    }
+
+    // A 1920x1080 monitor with a taller 1440x2560 portrait one to its right,
+    // leaving a gap below the first one
+    fn side_by_side() -> Vec<MonitorInfo> {
+        vec![monitor(0, 0, 1920, 1080), monitor(1920, 0, 3360, 2560)]
+    }
+
+    #[test]
+    fn largest_overlap_wins() {
+        let monitors = side_by_side();
+        // Mostly on the right monitor
+        assert_eq!(current_monitor(1800, 100, 2800, 700, &monitors), Some(1));
+        // Mostly on the left monitor
+        assert_eq!(current_monitor(1000, 100, 2000, 700, &monitors), Some(0));
+    }
+
+    #[test]
+    fn equal_overlap_goes_to_the_first_monitor() {
+        let monitors = side_by_side();
+        assert_eq!(current_monitor(1820, 100, 2020, 300, &monitors), Some(0));
+
+        let reversed: Vec<MonitorInfo> = monitors.into_iter().rev().collect();
+        assert_eq!(current_monitor(1820, 100, 2020, 300, &reversed), Some(0));
+    }
+
+    #[test]
+    fn window_in_a_gap_goes_to_the_nearest_monitor() {
+        let monitors = side_by_side();
+        // Below the left monitor, closer to it than to the right one
+        assert_eq!(current_monitor(200, 1200, 600, 1400, &monitors), Some(0));
+        // Below the left monitor, but next to the right one
+        assert_eq!(current_monitor(1700, 2000, 1900, 2200, &monitors), Some(1));
+    }
+
+    #[test]
+    fn window_off_screen_goes_to_the_nearest_monitor() {
+        let monitors = side_by_side();
+        assert_eq!(current_monitor(-5000, -5000, -4000, -4000, &monitors), Some(0));
+        assert_eq!(current_monitor(9000, 3000, 9500, 3500, &monitors), Some(1));
+    }
+
+    #[test]
+    fn no_monitors_gives_none() {
+        assert_eq!(current_monitor(0, 0, 100, 100, &[]), None);
+    }
+
+    // `monitor` at `percent` scaling
+    fn scaled(monitor: MonitorInfo, percent: u32) -> MonitorInfo {
+        MonitorInfo {
+            dpi: 96 * percent / 100,
+            scale_factor: percent as f32 / 100.0,
+            ..monitor
+        }
+    }
+
+    // Moving a window of `size` centered at `center` from monitor `from` to
+    // monitor `to`
+    fn moving(monitors: &[MonitorInfo], from: usize, to: usize, size: (i32, i32), center: (i32, i32)) -> ActionContext {
+        ActionContext {
+            current_monitor: from,
+            target_monitor: to,
+            monitors: monitors.to_vec(),
+            window_info: WindowInfo {
+                current_width: size.0,
+                current_height: size.1,
+                center_x: center.0,
+                center_y: center.1,
+            },
+        }
+    }
+
+    #[test]
+    fn logical_values_scale_with_the_monitor() {
+        let monitor = monitor(0, 0, 1920, 1080);
+        assert_eq!(scaled(monitor.clone(), 100).to_physical(32), 32);
+        assert_eq!(scaled(monitor.clone(), 125).to_physical(32), 40);
+        assert_eq!(scaled(monitor.clone(), 150).to_physical(32), 48);
+        assert_eq!(scaled(monitor.clone(), 175).to_physical(5), 9);
+        assert_eq!(scaled(monitor, 200).to_physical(-8), -16);
+    }
+
+    #[test]
+    fn windows_keep_their_logical_size_across_scale_factors() {
+        // A 1080p monitor at 100% next to a 4K one at 150%
+        let monitors = [scaled(monitor(0, 0, 1920, 1080), 100), scaled(monitor(1920, 0, 5760, 2160), 150)];
+
+        // 800x600 in the middle of the first monitor is 1200x900 in the middle of the second
+        let (width, height, x, y) = carry_over(&moving(&monitors, 0, 1, (800, 600), (960, 540)));
+        assert_eq!((width, height), (1200, 900));
+        assert_eq!((x + width / 2, y + height / 2), (1920 + 1920, 1080));
+
+        // And back again
+        let (width, height, x, y) = carry_over(&moving(&monitors, 1, 0, (1200, 900), (3840, 1080)));
+        assert_eq!((width, height, x, y), (800, 600, 560, 240));
+    }
+
+    #[test]
+    fn windows_keep_their_relative_position() {
+        // A 1440p monitor at 200% left of a 1080p one at 100%
+        let monitors = [scaled(monitor(-2560, 0, 0, 1440), 200), scaled(monitor(0, 0, 1920, 1080), 100)];
+
+        // Centered a quarter of the way across and down
+        let (width, height, x, y) = carry_over(&moving(&monitors, 0, 1, (1000, 400), (-1920, 360)));
+        assert_eq!((width, height), (500, 200));
+        assert_eq!((x + width / 2, y + height / 2), (480, 270));
+    }
+
+    #[test]
+    fn windows_are_clamped_to_the_target_work_area() {
+        let monitors = [scaled(monitor(0, 0, 1920, 1080), 100), scaled(monitor(1920, 0, 3840, 1080), 200)];
+
+        // Twice as large doesn't fit, so the window fills the work area
+        let (width, height, x, y) = carry_over(&moving(&monitors, 0, 1, (1600, 900), (960, 540)));
+        assert_eq!((width, height, x, y), (1920, 1080, 1920, 0));
+
+        // Near the edge it's pushed back inside
+        let (width, height, x, y) = carry_over(&moving(&monitors, 0, 1, (400, 300), (1900, 1060)));
+        assert_eq!((width, height), (800, 600));
+        assert_eq!((x + width, y + height), (3840, 1080));
+    }
+}
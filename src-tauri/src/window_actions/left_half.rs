@@ -1,4 +1,5 @@
 use crate::ActionContext;
+use crate::window_actions::geometry::tile;
 use crate::window_actions::WindowActionHandler;
 use winapi::shared::windef::HWND;
 
@@ -11,9 +12,8 @@ impl WindowActionHandler for LeftHalfAction {
         let target_work_height = target_monitor_info.work_bottom - target_monitor_info.work_top;
 
         // Snap to left half of the monitor
-        let new_width = target_work_width / 2;
+        let (new_x, new_width) = tile(target_monitor_info.work_left, target_work_width, 2, 0, 1);
         let new_height = target_work_height;
-        let new_x = target_monitor_info.work_left;
         let new_y = target_monitor_info.work_top;
 
         println!("Snapping to left half: {}x{} at ({},{})", new_width, new_height, new_x, new_y);
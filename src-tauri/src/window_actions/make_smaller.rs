@@ -11,9 +11,10 @@ impl WindowActionHandler for MakeSmallerAction {
         let new_width = ((context.window_info.current_width as f32) * size_decrease_factor) as i32;
         let new_height = ((context.window_info.current_height as f32) * size_decrease_factor) as i32;
 
-        // Ensure minimum window size (at least 200x150)
-        let final_width = new_width.max(200);
-        let final_height = new_height.max(150);
+        // Ensure minimum window size (at least 200x150 logical pixels)
+        let target_monitor_info = &context.monitors[context.target_monitor];
+        let final_width = new_width.max(target_monitor_info.to_physical(200));
+        let final_height = new_height.max(target_monitor_info.to_physical(150));
 
         // Center the window with new size
         let new_x = context.window_info.center_x - (final_width / 2);
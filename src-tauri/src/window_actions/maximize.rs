@@ -7,25 +7,21 @@ pub struct MaximizeAction {
 }
 
 impl WindowActionHandler for MaximizeAction {
-    fn calculate_position(&self, _context: &ActionContext, hwnd: HWND) -> Result<(i32, i32, i32, i32), String> {
+    fn calculate_position(&self, context: &ActionContext, hwnd: HWND) -> Result<(i32, i32, i32, i32), String> {
         use winapi::shared::windef::RECT;
         use winapi::um::dwmapi::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
-        use winapi::um::winuser::{GetDpiForWindow, GetMonitorInfoW, GetWindowRect, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST};
+        use winapi::um::winuser::GetWindowRect;
 
         unsafe {
-            // Get the monitor info for proper work area
-            let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
-            let mut monitor_info: MONITORINFO = std::mem::zeroed();
-            monitor_info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
-
-            if GetMonitorInfoW(monitor, &mut monitor_info) == 0 {
-                return Err("Failed to get monitor info".to_string());
-            }
-
-            // Use the actual work area from monitor info
-            let work_area = monitor_info.rcWork;
-            let dpi = GetDpiForWindow(hwnd);
-            let dpi_scale = dpi as f32 / 96.0; // 96 is the default DPI
+            // Work area and DPI both come from the target monitor, which
+            // differs from the window's one during a cross-monitor action
+            let target_monitor_info = &context.monitors[context.target_monitor];
+            let work_area = RECT {
+                left: target_monitor_info.work_left,
+                top: target_monitor_info.work_top,
+                right: target_monitor_info.work_right,
+                bottom: target_monitor_info.work_bottom,
+            };
 
             // Get the extended frame bounds first to understand current shadow size
             let mut window_rect = std::mem::zeroed();
@@ -47,8 +43,8 @@ impl WindowActionHandler for MaximizeAction {
             let shadow_right = window_rect.right - extended_frame.right;
             let shadow_bottom = window_rect.bottom - extended_frame.bottom;
 
-            // Calculate dimensions accounting for DPI and gutter
-            let scaled_gutter = (self.gutter as f32 * dpi_scale) as i32;
+            // The gutter is in logical pixels, convert it for the target monitor's DPI
+            let scaled_gutter = target_monitor_info.to_physical(self.gutter);
             let work_width = work_area.right - work_area.left;
             let work_height = work_area.bottom - work_area.top;
 
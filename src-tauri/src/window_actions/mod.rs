@@ -24,6 +24,7 @@ mod center;
 mod center_third;
 mod first_third;
 mod first_two_thirds;
+pub mod geometry;
 mod last_third;
 mod last_two_thirds;
 mod left_half;
@@ -33,6 +34,7 @@ mod maximize;
 mod maximize_height;
 mod move_left;
 mod move_right;
+mod move_to_monitor;
 mod no_op;
 mod right_half;
 mod top_half;
@@ -55,6 +57,7 @@ pub use maximize::MaximizeAction;
 pub use maximize_height::MaximizeHeightAction;
 pub use move_left::MoveLeftAction;
 pub use move_right::MoveRightAction;
+pub use move_to_monitor::MoveToMonitorAction;
 pub use no_op::NoOpAction;
 pub use right_half::RightHalfAction;
 pub use top_half::TopHalfAction;
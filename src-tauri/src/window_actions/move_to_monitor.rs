@@ -0,0 +1,24 @@
+use crate::{ActionContext, MonitorInfo};
+use crate::window_actions::{geometry, WindowActionHandler};
+use winapi::shared::windef::HWND;
+
+/// Moves the window to a monitor picked by its number, see [`geometry::numbered`]
+pub struct MoveToMonitorAction {
+    pub number: usize,
+}
+
+impl WindowActionHandler for MoveToMonitorAction {
+    fn get_target_monitor(&self, current_monitor: usize, monitors: &[MonitorInfo]) -> usize {
+        // Unknown numbers leave the window where it is
+        geometry::numbered(monitors)
+            .get(self.number.wrapping_sub(1))
+            .copied()
+            .unwrap_or(current_monitor)
+    }
+
+    fn calculate_position(&self, context: &ActionContext, _hwnd: HWND) -> Result<(i32, i32, i32, i32), String> {
+        let (new_width, new_height, new_x, new_y) = geometry::carry_over(context);
+        println!("Moving to monitor {}: {}x{} at ({},{})", self.number, new_width, new_height, new_x, new_y);
+        Ok((new_width, new_height, new_x, new_y))
+    }
+}
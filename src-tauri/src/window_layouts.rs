@@ -0,0 +1,160 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use winapi::shared::windef::HWND;
+use winapi::um::winuser::{IsIconic, IsZoomed, ShowWindow, SW_RESTORE};
+
+use crate::control;
+use crate::registry;
+use crate::shortcuts::ShortcutsConfig;
+use crate::suspend;
+use crate::window_actions::geometry;
+
+/// Where the windows of one application go when a layout is applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Placement {
+    /// Executable name of the windows to place, e.g. `code.exe`
+    pub process: String,
+    /// Only place windows whose title contains this text, ignoring case
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Id of the action that places the windows, e.g. `leftHalf`
+    pub action: String,
+    /// Monitor to place the windows on, counting from 1 on the left. Windows
+    /// stay on their own monitor when missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<usize>,
+}
+
+impl Placement {
+    // Returns true for the windows of `process` titled `title` this placement is for
+    fn matches(&self, process: &str, title: &str) -> bool {
+        self.process.eq_ignore_ascii_case(process)
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|text| title.to_lowercase().contains(&text.to_lowercase()))
+    }
+}
+
+// Moves one window as `placement` says, restoring it first if it's minimized
+// or maximized so it can be sized
+fn place(hwnd: HWND, placement: &Placement, monitor: Option<usize>) -> Result<(), String> {
+    let action = registry::find(&placement.action)
+        .ok_or_else(|| format!("Unknown action: {}", placement.action))?
+        .action
+        .clone();
+    unsafe {
+        if IsIconic(hwnd) != 0 || IsZoomed(hwnd) != 0 {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+    }
+    crate::place_window(hwnd, &action, monitor)
+}
+
+/// Applies the layout `name` of `config`: every open window matching one of
+/// its placements is moved there. The first matching placement wins, and
+/// placements for monitors that aren't connected are skipped. Returns what
+/// was done.
+pub fn apply(config: &ShortcutsConfig, name: &str) -> Result<String, String> {
+    let placements = config.layouts.get(name).ok_or_else(|| {
+        if config.layouts.is_empty() {
+            format!("Unknown layout: {} (no layouts are set up in the settings file)", name)
+        } else {
+            let names: Vec<&str> = config.layouts.keys().map(String::as_str).collect();
+            format!("Unknown layout: {} (expected one of {})", name, names.join(", "))
+        }
+    })?;
+
+    // Check the whole layout before moving anything
+    for placement in placements {
+        if registry::find(&placement.action).is_none() {
+            return Err(format!("Layout {} uses an unknown action: {}", name, placement.action));
+        }
+    }
+
+    crate::get_monitor_info();
+    let numbers = geometry::numbered(&crate::MONITORS.lock().unwrap());
+
+    let mut placed = vec![0; placements.len()];
+    let mut notes = Vec::new();
+    for hwnd in control::app_windows() {
+        let Some(process) = (unsafe { suspend::process_name(hwnd) }) else {
+            continue;
+        };
+        let title = unsafe { control::window_title(hwnd) };
+        let Some(index) = placements.iter().position(|placement| placement.matches(&process, &title)) else {
+            continue;
+        };
+
+        let placement = &placements[index];
+        let monitor = match placement.monitor {
+            Some(number) => match numbers.get(number.wrapping_sub(1)) {
+                Some(monitor) => Some(*monitor),
+                None => continue,
+            },
+            None => None,
+        };
+        match place(hwnd, placement, monitor) {
+            Ok(()) => placed[index] += 1,
+            Err(e) => notes.push(format!("failed to place {} ({}): {}", title, process, e)),
+        }
+    }
+
+    for (placement, count) in placements.iter().zip(&placed) {
+        match placement.monitor {
+            Some(number) if !(1..=numbers.len()).contains(&number) => {
+                notes.push(format!("no monitor {} for {} (1..={})", number, placement.process, numbers.len()))
+            }
+            _ if *count == 0 => notes.push(format!("no window of {} is open", placement.process)),
+            _ => {}
+        }
+    }
+
+    let total: usize = placed.iter().sum();
+    let mut message = format!("Applied layout {}: placed {} windows", name, total);
+    if !notes.is_empty() {
+        message.push_str(&format!(", {}", notes.join(", ")));
+    }
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placement(process: &str, title: Option<&str>) -> Placement {
+        Placement {
+            process: process.to_string(),
+            title: title.map(str::to_string),
+            action: "leftHalf".to_string(),
+            monitor: None,
+        }
+    }
+
+    #[test]
+    fn process_names_match_ignoring_case() {
+        assert!(placement("code.exe", None).matches("Code.exe", "main.rs - Visual Studio Code"));
+        assert!(!placement("code.exe", None).matches("slack.exe", "Slack"));
+    }
+
+    #[test]
+    fn title_narrows_the_match() {
+        let outlook = placement("msedge.exe", Some("outlook"));
+        assert!(outlook.matches("msedge.exe", "Mail - Outlook - Microsoft Edge"));
+        assert!(!outlook.matches("msedge.exe", "GitHub - Microsoft Edge"));
+        assert!(!outlook.matches("chrome.exe", "Mail - Outlook - Google Chrome"));
+    }
+
+    #[test]
+    fn placements_read_from_settings() {
+        let placements: Vec<Placement> = serde_json::from_str(
+            r#"[{ "process": "code.exe", "action": "firstTwoThirds", "monitor": 1 },
+                { "process": "msedge.exe", "title": "Outlook", "action": "maximizeWindow" }]"#,
+        )
+        .unwrap();
+        assert_eq!(placements[0].monitor, Some(1));
+        assert_eq!(placements[1].title.as_deref(), Some("Outlook"));
+        assert!(placements.iter().all(|placement| registry::find(&placement.action).is_some()));
+    }
+}
@@ -0,0 +1,241 @@
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::registry;
+use crate::shortcuts::{self, parse_shortcut};
+use crate::Action;
+
+// Pressing this leaves window mode
+const EXIT_KEY: &str = "Escape";
+
+/// Settings of the modal "window mode", in which plain keys perform actions
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WindowModeConfig {
+    /// Shortcut that enters window mode, leave empty to disable it
+    pub shortcut: String,
+    /// Window mode is left after this many milliseconds without a key press
+    pub timeout_ms: u64,
+    /// Maps each key to the id of the action it triggers in window mode
+    pub keys: BTreeMap<String, String>,
+}
+
+impl Default for WindowModeConfig {
+    fn default() -> Self {
+        let keys = [
+            // Vim-style halves
+            ("H", "leftHalf"),
+            ("J", "bottomHalf"),
+            ("K", "topHalf"),
+            ("L", "rightHalf"),
+            // Number keys laid out like a numpad over the screen
+            ("1", "bottomLeft"),
+            ("2", "bottomHalf"),
+            ("3", "bottomRight"),
+            ("4", "leftHalf"),
+            ("5", "center"),
+            ("6", "rightHalf"),
+            ("7", "topLeft"),
+            ("8", "topHalf"),
+            ("9", "topRight"),
+            // Resizing
+            ("Equal", "makeLarger"),
+            ("Minus", "makeSmaller"),
+            ("Enter", "maximizeWindow"),
+        ];
+
+        Self {
+            shortcut: "Control+Alt+Space".to_string(),
+            timeout_ms: 5000,
+            keys: keys
+                .iter()
+                .map(|(key, id)| (key.to_string(), id.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Window mode keys resolved against the action registry
+pub struct WindowModeKeys {
+    pub keys: HashMap<u32, (Shortcut, String, Action)>,
+    pub timeout: Duration,
+}
+
+impl WindowModeConfig {
+    /// Parses the shortcut that enters window mode; `None` if it's disabled
+    pub fn parse(&self) -> Result<Option<Shortcut>, String> {
+        if self.shortcut.trim().is_empty() {
+            return Ok(None);
+        }
+        parse_shortcut(self.shortcut.trim())
+            .map(Some)
+            .map_err(|e| format!("Invalid window mode shortcut: {}", e))
+    }
+
+    /// Returns the shortcut that enters window mode, if it's enabled and valid
+    pub fn activation_shortcut(&self) -> Option<Shortcut> {
+        self.parse().ok().flatten()
+    }
+
+    /// Parses the key map, returning the valid keys along with an error
+    /// message for each key or action id that couldn't be resolved
+    pub fn resolve(&self) -> (WindowModeKeys, Vec<String>) {
+        let mut keys = HashMap::new();
+        let mut errors = Vec::new();
+
+        for (key, action_id) in &self.keys {
+            let Some(descriptor) = registry::find(action_id) else {
+                errors.push(format!("Unknown action '{}' for window mode key '{}'", action_id, key));
+                continue;
+            };
+            match parse_shortcut(key) {
+                Ok(shortcut) => {
+                    keys.insert(shortcut.id(), (shortcut, action_id.clone(), descriptor.action.clone()));
+                }
+                Err(e) => errors.push(format!("Invalid window mode key '{}': {}", key, e)),
+            }
+        }
+
+        let resolved = WindowModeKeys {
+            keys,
+            timeout: Duration::from_millis(self.timeout_ms),
+        };
+        (resolved, errors)
+    }
+}
+
+struct ActiveMode {
+    generation: u64,
+    keys: HashMap<u32, (String, Action)>,
+    exit_key: Option<u32>,
+    // Keys we registered for window mode and must release on exit
+    grabbed: Vec<Shortcut>,
+    last_key: Instant,
+}
+
+// Window mode state while it's active
+static ACTIVE_MODE: Lazy<Mutex<Option<ActiveMode>>> = Lazy::new(|| Mutex::new(None));
+
+// Bumped every time window mode is entered so stale timers can tell they're no longer relevant
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// What a key press means while window mode is active
+pub enum ModeKey {
+    Action(String, Action),
+    Exit,
+}
+
+/// Enters window mode, grabbing its keys until it's left with `Escape` or
+/// times out after `timeout` without a key press.
+///
+/// Runs inside the global shortcut handler, so the keys are grabbed on
+/// another thread.
+pub fn enter(app_handle: &AppHandle, mode: &WindowModeKeys) {
+    if is_active() {
+        return;
+    }
+
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let exit_key = Shortcut::from_str(EXIT_KEY).ok();
+    let mut to_grab: Vec<Shortcut> = mode.keys.values().map(|(shortcut, _, _)| *shortcut).collect();
+    to_grab.extend(exit_key);
+
+    *ACTIVE_MODE.lock().unwrap() = Some(ActiveMode {
+        generation,
+        keys: mode
+            .keys
+            .iter()
+            .map(|(id, (_, action_id, action))| (*id, (action_id.clone(), action.clone())))
+            .collect(),
+        exit_key: exit_key.map(|key| key.id()),
+        grabbed: Vec::new(),
+        last_key: Instant::now(),
+    });
+    println!("Entered window mode");
+
+    let timeout = mode.timeout;
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        let grabbed = shortcuts::grab_keys(&app_handle, to_grab);
+
+        let stale = {
+            let mut active = ACTIVE_MODE.lock().unwrap();
+            match active.as_mut() {
+                Some(mode) if mode.generation == generation => {
+                    mode.grabbed = grabbed;
+                    None
+                }
+                // Window mode was already left while we were grabbing keys
+                _ => Some(grabbed),
+            }
+        };
+        if let Some(grabbed) = stale {
+            shortcuts::release_keys(&app_handle, grabbed);
+        }
+        crate::refresh_tray_tooltip(&app_handle);
+
+        // Every key press pushes the timeout back
+        loop {
+            let remaining = {
+                let active = ACTIVE_MODE.lock().unwrap();
+                match active.as_ref() {
+                    Some(mode) if mode.generation == generation => {
+                        timeout.checked_sub(mode.last_key.elapsed())
+                    }
+                    _ => return,
+                }
+            };
+            match remaining {
+                Some(remaining) if !remaining.is_zero() => std::thread::sleep(remaining),
+                _ => break,
+            }
+        }
+
+        println!("Window mode timed out");
+        exit(&app_handle);
+    });
+}
+
+/// Handles a key press while window mode is active. Returns `None` when
+/// window mode isn't active or the key isn't one of its keys.
+pub fn handle_key(app_handle: &AppHandle, shortcut_id: u32) -> Option<ModeKey> {
+    let mut active = ACTIVE_MODE.lock().unwrap();
+    let mode = active.as_mut()?;
+
+    if mode.exit_key == Some(shortcut_id) {
+        drop(active);
+        exit(app_handle);
+        return Some(ModeKey::Exit);
+    }
+
+    let (action_id, action) = mode.keys.get(&shortcut_id)?.clone();
+    mode.last_key = Instant::now();
+    Some(ModeKey::Action(action_id, action))
+}
+
+/// Leaves window mode and releases its keys
+pub fn exit(app_handle: &AppHandle) {
+    let Some(mode) = ACTIVE_MODE.lock().unwrap().take() else {
+        return;
+    };
+    println!("Left window mode");
+
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        shortcuts::release_keys(&app_handle, mode.grabbed);
+        crate::refresh_tray_tooltip(&app_handle);
+    });
+}
+
+/// Returns true while window mode is active
+pub fn is_active() -> bool {
+    ACTIVE_MODE.lock().unwrap().is_some()
+}